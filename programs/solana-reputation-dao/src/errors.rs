@@ -52,4 +52,122 @@ pub enum ReputationError {
     ProfileNotInitialized,
     #[msg("Realm creation failed")]
     RealmCreationFailed,
-}
\ No newline at end of file
+    #[msg("Proposal title too long")]
+    ProposalTitleTooLong,
+    #[msg("Invalid vote type")]
+    InvalidVoteType,
+    #[msg("Proposal is not active")]
+    ProposalNotActive,
+    #[msg("Voting period has ended")]
+    VotingPeriodEnded,
+    #[msg("Voting period still active")]
+    VotingPeriodActive,
+    #[msg("Already a member of this realm")]
+    AlreadyRealmMember,
+    #[msg("All realm membership slots are full")]
+    MembershipSlotsFull,
+    #[msg("Retention period has not elapsed")]
+    RetentionPeriodActive,
+    #[msg("Realm does not match the account's realm")]
+    RealmMismatch,
+    #[msg("Not allowed to open a dispute on this interaction")]
+    DisputeNotAuthorized,
+    #[msg("Interaction has an unresolved dispute")]
+    DisputeOpen,
+    #[msg("Dispute is not in the required state")]
+    InvalidDisputeState,
+    #[msg("Trust slash too high")]
+    TrustSlashTooHigh,
+    #[msg("Badge definition must name an issuer, a merkle root or auto-award criteria")]
+    BadgeDefinitionUnverifiable,
+    #[msg("Inline badge slots are full; pass a BadgeCollection page with room")]
+    BadgeStorageFull,
+    #[msg("Badge not found in the provided storage")]
+    BadgeNotFound,
+    #[msg("Badge token accounts are missing or do not match")]
+    InvalidBadgeToken,
+    #[msg("Badge name or URI too long")]
+    BadgeMetadataTooLong,
+    #[msg("Invalid badge category")]
+    InvalidBadgeCategory,
+    #[msg("Badge score bonus too high")]
+    BadgeScoreBonusTooHigh,
+    #[msg("Badge supply exhausted")]
+    BadgeSupplyExhausted,
+    #[msg("Badge definition no longer accepts claims")]
+    BadgeDefinitionExpired,
+    #[msg("Badge eligibility requirements not met")]
+    BadgeEligibilityNotMet,
+    #[msg("Profile does not meet the badge's award criteria")]
+    BadgeCriteriaNotMet,
+    #[msg("Badge validity period cannot be negative")]
+    InvalidBadgeValidity,
+    #[msg("Realm uses local reputation; pass the member's RealmReputation")]
+    RealmReputationRequired,
+    #[msg("Attestation signature or contents do not verify")]
+    InvalidAttestation,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    #[msg("Bridge nonce or attestation already used")]
+    BridgeReplay,
+    #[msg("Bridge cooldown has not elapsed")]
+    BridgeCooldownActive,
+    #[msg("Bridged reputation cap reached for this realm pair")]
+    BridgeCapReached,
+    #[msg("Delegation has expired or the expiry is in the past")]
+    DelegationExpired,
+    #[msg("Only the delegator can revoke an unexpired delegation")]
+    DelegationNotExpired,
+    #[msg("Badge has not expired")]
+    BadgeNotExpired,
+    #[msg("Badge definition does not issue expiring badges")]
+    BadgeDoesNotExpire,
+    #[msg("Category mask must cover some of the five categories or general proposals, and nothing else")]
+    InvalidCategoryMask,
+    #[msg("Invalid proposal category")]
+    InvalidProposalCategory,
+    #[msg("Delegation would create a cycle")]
+    DelegationCycle,
+    #[msg("Delegation chain is longer than allowed")]
+    DelegationDepthExceeded,
+    #[msg("Delegation chain accounts are missing, out of order or do not resolve to the voter")]
+    InvalidDelegationChain,
+    #[msg("Already voted on this proposal")]
+    AlreadyVoted,
+    #[msg("Realm requires multiple admin approvals; propose an AdminAction or lower the threshold")]
+    MultisigRequired,
+    #[msg("Admin change targets a missing, duplicate or empty admin")]
+    InvalidAdminChange,
+    #[msg("All admin slots are in use")]
+    AdminSlotsFull,
+    #[msg("Admin threshold must be between 1 and the number of admins")]
+    InvalidAdminThreshold,
+    #[msg("Admin has already approved this action")]
+    AdminActionAlreadyApproved,
+    #[msg("Admin action has not reached the approval threshold")]
+    AdminActionNotApproved,
+    #[msg("Admin action was already executed")]
+    AdminActionExecuted,
+    #[msg("Decay factor or cross-realm factor is out of range")]
+    InvalidAlgorithmParameters,
+    #[msg("Interaction retention must cover the dispute window")]
+    RetentionTooShort,
+    #[msg("Dispute window for this interaction has closed")]
+    DisputeWindowClosed,
+    #[msg("Realm-less interactions can only be disputed in a realm the receiver belongs to")]
+    DisputeRealmRequired,
+    #[msg("Auto-award criteria must require a minimum of earned category reputation")]
+    AutoAwardCriteriaTooWeak,
+    #[msg("Admin action was not executed in time")]
+    AdminActionExpired,
+    #[msg("Reputation votes are limited to one per wallet per day")]
+    ReputationVoteCooldown,
+    #[msg("Bridged reputation has no category scores to credit in proportion to")]
+    BridgeSourceEmpty,
+    #[msg("Category is already delegated to someone else; revoke or move that delegation first")]
+    DelegationCategoryTaken,
+    #[msg("Fewer realm admins signed than the admin threshold requires")]
+    AdminQuorumRequired,
+    #[msg("Realm admins changed since this action was proposed; propose it again")]
+    AdminActionStale,
+}
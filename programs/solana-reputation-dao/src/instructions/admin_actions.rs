@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, AdminAction, AdminActionKind};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        init,
+        payer = admin,
+        space = AdminAction::LEN,
+        seeds = [b"admin_action", realm.key().as_ref(), &realm.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub admin_action: Account<'info, AdminAction>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, has_one = realm)]
+    pub admin_action: Account<'info, AdminAction>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, has_one = realm)]
+    pub admin_action: Account<'info, AdminAction>,
+    /// Anyone may execute once the threshold is met
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAdminAction<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, has_one = realm, has_one = proposer, close = proposer)]
+    pub admin_action: Account<'info, AdminAction>,
+    /// CHECK: receives the action's rent back; bound by has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub closer: Signer<'info>,
+}
+
+// The proposer's approval is recorded with the proposal
+pub(crate) fn propose_handler(ctx: Context<ProposeAdminAction>, kind: AdminActionKind) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    require!(ctx.accounts.realm.is_admin(&admin), ReputationError::AdminRequired);
+    // Reject changes that could never execute against the current config
+    (*ctx.accounts.realm).clone().apply_admin_action(&kind)?;
+
+    let clock = Clock::get()?;
+    let realm_key = ctx.accounts.realm.key();
+    let realm = &mut ctx.accounts.realm;
+    let action_id = realm.admin_action_count;
+
+    let action = &mut ctx.accounts.admin_action;
+    action.realm = realm_key;
+    action.action_id = action_id;
+    action.proposer = admin;
+    action.kind = kind;
+    action.approvals = [admin, Pubkey::default(), Pubkey::default()];
+    action.created_at = clock.unix_timestamp;
+    action.executed = false;
+    action.bump = ctx.bumps.admin_action;
+    action.admin_epoch = realm.admin_epoch;
+
+    realm.admin_action_count = realm.admin_action_count.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+
+    emit!(AdminActionProposed {
+        realm: realm_key,
+        admin_action: action.key(),
+        action_id,
+        proposer: admin,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn approve_handler(ctx: Context<ApproveAdminAction>) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let realm = &ctx.accounts.realm;
+    require!(realm.is_admin(&admin), ReputationError::AdminRequired);
+
+    let action = &mut ctx.accounts.admin_action;
+    require!(!action.executed, ReputationError::AdminActionExecuted);
+    require!(!action.is_expired(Clock::get()?.unix_timestamp), ReputationError::AdminActionExpired);
+    require!(!action.is_stale(realm), ReputationError::AdminActionStale);
+    require!(!action.approvals.contains(&admin), ReputationError::AdminActionAlreadyApproved);
+    let slot = action
+        .approvals
+        .iter_mut()
+        .find(|a| **a == Pubkey::default())
+        .ok_or(ReputationError::AdminActionAlreadyApproved)?;
+    *slot = admin;
+
+    emit!(AdminActionApproved {
+        admin_action: action.key(),
+        admin,
+        approvals: action.approval_count(realm),
+    });
+
+    Ok(())
+}
+
+pub(crate) fn execute_handler(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+    let action = &mut ctx.accounts.admin_action;
+    require!(!action.executed, ReputationError::AdminActionExecuted);
+    // Stale approvals must not apply a change long after the fact
+    require!(!action.is_expired(Clock::get()?.unix_timestamp), ReputationError::AdminActionExpired);
+    let realm = &mut ctx.accounts.realm;
+    require!(!action.is_stale(realm), ReputationError::AdminActionStale);
+    require!(
+        action.approval_count(realm) >= realm.admin_threshold.max(1),
+        ReputationError::AdminActionNotApproved
+    );
+
+    realm.apply_admin_action(&action.kind)?;
+    action.executed = true;
+
+    emit!(AdminActionExecuted {
+        realm: realm.key(),
+        admin_action: action.key(),
+        action_id: action.action_id,
+        executed_by: ctx.accounts.executor.key(),
+    });
+
+    Ok(())
+}
+
+// Admins may cancel a pending action; once it was executed, expired or went
+// stale, anyone may close it. Either way the rent goes back to the proposer.
+pub(crate) fn close_handler(ctx: Context<CloseAdminAction>) -> Result<()> {
+    let realm = &ctx.accounts.realm;
+    let action = &ctx.accounts.admin_action;
+    let closer = ctx.accounts.closer.key();
+    let settled = action.executed || action.is_expired(Clock::get()?.unix_timestamp) || action.is_stale(realm);
+    require!(settled || realm.is_admin(&closer), ReputationError::AdminRequired);
+
+    emit!(AdminActionClosed {
+        realm: realm.key(),
+        admin_action: action.key(),
+        action_id: action.action_id,
+        executed: action.executed,
+        closed_by: closer,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminActionProposed {
+    pub realm: Pubkey,
+    pub admin_action: Pubkey,
+    pub action_id: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct AdminActionApproved {
+    pub admin_action: Pubkey,
+    pub admin: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct AdminActionExecuted {
+    pub realm: Pubkey,
+    pub admin_action: Pubkey,
+    pub action_id: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct AdminActionClosed {
+    pub realm: Pubkey,
+    pub admin_action: Pubkey,
+    pub action_id: u64,
+    pub executed: bool,
+    pub closed_by: Pubkey,
+}
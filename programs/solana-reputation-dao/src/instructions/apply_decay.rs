@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation};
+use crate::utils::reputation_math;
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct ApplyDecay<'info> {
+    #[account(mut, seeds = [b"reputation", profile.wallet.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    /// Realm whose decay_factor applies to `realm_reputation`; never to the profile
+    pub realm: Option<Account<'info, GovernanceRealm>>,
+    /// Profile's ledger in `realm`, required together with it
+    #[account(mut)]
+    pub realm_reputation: Option<Account<'info, RealmReputation>>,
+    pub caller: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ApplyDecay>) -> Result<()> {
+    let clock = Clock::get()?;
+    let profile = &mut ctx.accounts.profile;
+
+    // Each score decays at its owner's rate: the profile at its own, a ledger
+    // at its realm's. The caller picking a realm never changes either.
+    match (ctx.accounts.realm.as_ref(), ctx.accounts.realm_reputation.as_mut()) {
+        (Some(realm), Some(ledger)) => {
+            ledger.ensure_for(&realm.realm_id, &profile.wallet)?;
+            let rate = realm.reputation_algorithm.decay_factor;
+            let decayed = reputation_math::calculate_realm_reputation_decay(ledger, rate, clock.unix_timestamp)?;
+            if decayed > 0 {
+                emit!(ReputationDecayed {
+                    wallet: profile.wallet,
+                    realm: realm.realm_id,
+                    amount: decayed,
+                    new_total: ledger.total_score,
+                    decay_rate: rate,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+        (None, None) => {}
+        _ => return err!(ReputationError::RealmMismatch),
+    }
+
+    let rate = profile.reputation_decay_rate;
+    let decayed = reputation_math::calculate_reputation_decay(profile, rate, clock.unix_timestamp)?;
+    if decayed > 0 {
+        emit!(ReputationDecayed {
+            wallet: profile.wallet,
+            realm: Pubkey::default(),
+            amount: decayed,
+            new_total: profile.total_score,
+            decay_rate: rate,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationDecayed {
+    pub wallet: Pubkey,
+    pub realm: Pubkey, // Pubkey::default() for the global profile
+    pub amount: u64,
+    pub new_total: u64,
+    pub decay_rate: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    ReputationProfile, Badge, BadgeType, BadgeReceipt, BadgeDefinition, BadgeCollection, GovernanceRealm,
+    RealmReputation,
+};
+use crate::errors::ReputationError;
+use super::claim_badge::{check_claimable, store_badge};
+
+#[derive(Accounts)]
+pub struct AwardEarnedBadge<'info> {
+    #[account(mut, seeds = [b"reputation", owner.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    #[account(
+        init,
+        payer = payer,
+        space = BadgeReceipt::LEN,
+        seeds = [b"badge", owner.key().as_ref(), badge_definition.key().as_ref()],
+        bump
+    )]
+    pub badge_account: Account<'info, BadgeReceipt>,
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        mut,
+        has_one = realm,
+        seeds = [b"badge_def", realm.key().as_ref(), &badge_definition.definition_id.to_le_bytes()],
+        bump = badge_definition.bump
+    )]
+    pub badge_definition: Account<'info, BadgeDefinition>,
+    /// Overflow page, required once the inline badge slots are full
+    #[account(mut)]
+    pub badge_collection: Option<Box<Account<'info, BadgeCollection>>>,
+    /// Holder's ledger in `realm`, the only score the bonus lands in
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RealmReputation::LEN,
+        seeds = [b"realm_rep", realm.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub realm_reputation: Box<Account<'info, RealmReputation>>,
+    /// CHECK: profile owner receiving the badge; bound by the profile seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless: anyone may award a criteria badge to a profile that meets it
+pub(crate) fn handler(ctx: Context<AwardEarnedBadge>) -> Result<()> {
+    let clock = Clock::get()?;
+    let definition = &ctx.accounts.badge_definition;
+    let bt: BadgeType =
+        BadgeType::try_from(definition.badge_type).map_err(|_| error!(ReputationError::InvalidBadgeProof))?;
+    let issuer_realm = ctx.accounts.realm.realm_id;
+    check_claimable(definition, &ctx.accounts.profile, issuer_realm, clock.unix_timestamp)?;
+    require!(definition.criteria.is_met(&ctx.accounts.profile), ReputationError::BadgeCriteriaNotMet);
+
+    let category = definition.category as usize;
+    let definition_id = definition.definition_id;
+    let definition_key = definition.key();
+    let expires_at = definition.badge_expiry(clock.unix_timestamp);
+    let owner = ctx.accounts.owner.key();
+
+    // Only the room left under the realm's badge cap is credited
+    let ledger = &mut ctx.accounts.realm_reputation;
+    ledger.open(issuer_realm, owner, clock.unix_timestamp, ctx.bumps.realm_reputation);
+    let score_bonus = ledger.credit_badge(category, definition.score_bonus, clock.unix_timestamp);
+
+    let receipt = &mut ctx.accounts.badge_account;
+    receipt.owner = owner;
+    receipt.badge_type = bt as u8;
+    receipt.issuer_realm = issuer_realm;
+    receipt.definition = definition_key;
+    receipt.score_bonus = score_bonus;
+    receipt.category = category as u8;
+    receipt.proof_hash = [0u8; 32];
+    receipt.earned_at = clock.unix_timestamp;
+    receipt.expires_at = expires_at;
+    receipt.lapsed = false;
+    receipt.token_mint = Pubkey::default();
+    receipt.bump = ctx.bumps.badge_account;
+
+    let badge = Badge {
+        badge_type: bt,
+        definition_id,
+        earned_at: clock.unix_timestamp,
+        issuer_realm,
+        expires_at,
+        metadata_hash: [0u8; 32],
+    };
+    store_badge(
+        &mut ctx.accounts.profile,
+        ctx.accounts.badge_collection.as_deref_mut().map(|c| &mut **c),
+        badge,
+        category,
+    )?;
+    let definition = &mut ctx.accounts.badge_definition;
+    definition.supply = definition.supply.saturating_add(1);
+
+    emit!(BadgeAwarded {
+        owner,
+        definition: definition_key,
+        issuer_realm,
+        awarded_by: ctx.accounts.payer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeAwarded {
+    pub owner: Pubkey,
+    pub definition: Pubkey,
+    pub issuer_realm: Pubkey,
+    pub awarded_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,25 +1,102 @@
 use anchor_lang::prelude::*;
-use crate::state::{ReputationProfile, GovernanceRealm};
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation, BridgeRecord};
 use crate::errors::ReputationError;
 
 #[derive(Accounts)]
 pub struct BridgeReputation<'info> {
-    #[account(mut)]
+    #[account(mut, seeds = [b"reputation", user.key().as_ref()], bump = profile.bump)]
     pub profile: Account<'info, ReputationProfile>,
+    /// Realm the reputation was earned in
+    #[account(constraint = source_realm.key() != realm.key() @ ReputationError::BridgeOperationFailed)]
+    pub source_realm: Account<'info, GovernanceRealm>,
+    #[account(
+        seeds = [b"realm_rep", source_realm.key().as_ref(), user.key().as_ref()],
+        bump = source_reputation.bump
+    )]
+    pub source_reputation: Account<'info, RealmReputation>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = BridgeRecord::LEN,
+        seeds = [b"bridge", profile.key().as_ref(), source_realm.key().as_ref(), realm.key().as_ref()],
+        bump
+    )]
+    pub bridge_record: Account<'info, BridgeRecord>,
+    /// Destination realm whose cross-realm policy applies
     pub realm: Account<'info, GovernanceRealm>,
+    /// User's ledger in the destination realm, the only score the bridge credits
+    #[account(
+        mut,
+        seeds = [b"realm_rep", realm.key().as_ref(), user.key().as_ref()],
+        bump = destination_reputation.bump
+    )]
+    pub destination_reputation: Account<'info, RealmReputation>,
+    #[account(mut)]
     pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<BridgeReputation>, _source_realm: Pubkey, bridge_weight: u8) -> Result<()> {
-    require!(ctx.accounts.realm.cross_realm_enabled, ReputationError::CrossRealmDisabled);
-    require!(bridge_weight > 0, ReputationError::BridgeOperationFailed);
+// bridge_weight is the percentage (1-100) of the source-realm score to bridge;
+// nonce must equal the bridge record's current nonce
+pub(crate) fn handler(ctx: Context<BridgeReputation>, bridge_weight: u8, nonce: u64) -> Result<()> {
+    let realm = &ctx.accounts.realm;
+    let source_realm = &ctx.accounts.source_realm;
+    require!(
+        realm.cross_realm_enabled && source_realm.cross_realm_enabled,
+        ReputationError::CrossRealmDisabled
+    );
+    require!(bridge_weight > 0 && bridge_weight <= 100, ReputationError::BridgeOperationFailed);
+    let memberships = &ctx.accounts.profile.realm_memberships;
+    require!(
+        memberships.contains(&source_realm.realm_id) && memberships.contains(&realm.realm_id),
+        ReputationError::NotRealmMember
+    );
 
-    let factor = ctx.accounts.realm.reputation_algorithm.cross_realm_factor as u64;
-    let add = (bridge_weight as u64).saturating_mul(factor);
-    let profile = &mut ctx.accounts.profile;
+    let factor = realm.reputation_algorithm.cross_realm_factor as u128;
+    let derived = (ctx.accounts.source_reputation.total_score as u128)
+        .saturating_mul(bridge_weight as u128)
+        .saturating_mul(factor)
+        / 10_000;
+    let derived = derived.min(u64::MAX as u128) as u64;
+
+    let clock = Clock::get()?;
+    let record = &mut ctx.accounts.bridge_record;
+    record.profile = ctx.accounts.profile.key();
+    record.source_realm = source_realm.realm_id;
+    record.destination_realm = realm.realm_id;
+    record.bump = ctx.bumps.bridge_record;
+    let add = record.settle(nonce, derived, realm.max_bridged_reputation, clock.unix_timestamp)?;
 
+    // Realm-derived credit stays in realm ledgers; the global profile only
+    // tallies it in cross_dao_reputation, never in its scores
+    ctx.accounts
+        .destination_reputation
+        .credit_in_proportion(&ctx.accounts.source_reputation.category_scores, add, clock.unix_timestamp)?;
+    let profile = &mut ctx.accounts.profile;
     profile.cross_dao_reputation = profile.cross_dao_reputation.saturating_add(add);
-    profile.total_score = profile.total_score.saturating_add(add / 2);
+
+    emit!(ReputationBridged {
+        wallet: ctx.accounts.profile.wallet,
+        source_program: crate::ID,
+        source_realm: source_realm.realm_id,
+        realm: realm.realm_id,
+        amount: add,
+        total_bridged: record.total_bridged,
+        nonce,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
+
+#[event]
+pub struct ReputationBridged {
+    pub wallet: Pubkey,
+    pub source_program: Pubkey,
+    pub source_realm: Pubkey,
+    pub realm: Pubkey,
+    pub amount: u64,
+    pub total_bridged: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
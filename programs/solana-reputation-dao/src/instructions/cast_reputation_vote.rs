@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{ReputationProfile, GovernanceRealm};
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation};
+use crate::utils::constants::*;
 use crate::errors::ReputationError;
 
 #[derive(Accounts)]
@@ -12,25 +13,43 @@ pub struct CastReputationVote<'info> {
     pub voter_profile: Account<'info, ReputationProfile>,
     #[account(mut)]
     pub realm: Account<'info, GovernanceRealm>,
+    /// Voter's ledger in `realm`, credited alongside the global profile
+    #[account(
+        mut,
+        seeds = [b"realm_rep", realm.key().as_ref(), voter.key().as_ref()],
+        bump = voter_realm_reputation.bump
+    )]
+    pub voter_realm_reputation: Account<'info, RealmReputation>,
     #[account(mut)]
     pub voter: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<CastReputationVote>, vote_type: u8, justification: String) -> Result<()> {
+pub(crate) fn handler(ctx: Context<CastReputationVote>, vote_type: u8, justification: String) -> Result<()> {
     require!(justification.len() <= 280, ReputationError::MetadataTooLong);
     require!(
         ctx.accounts.voter_profile.total_score >= ctx.accounts.realm.min_reputation_threshold.min(100),
         ReputationError::InsufficientReputation
     );
+    require!(
+        ctx.accounts.voter_profile.realm_memberships.contains(&ctx.accounts.realm.realm_id),
+        ReputationError::NotRealmMember
+    );
     let increment: u64 = match vote_type {
         0 => 5,
         1 | 2 => 15,
         _ => return err!(ReputationError::InvalidActionType),
     };
+    // Each vote earns reputation in the profile and the realm ledger alike, so
+    // one cooldown covers both and local-reputation realms cannot be farmed
+    let now = Clock::get()?.unix_timestamp;
     let voter_profile = &mut ctx.accounts.voter_profile;
-    voter_profile.total_score = voter_profile.total_score.saturating_add(increment);
-    voter_profile.category_scores[1] =
-        voter_profile.category_scores[1].saturating_add(increment);
+    require!(
+        now >= voter_profile.last_reputation_vote_at.saturating_add(REPUTATION_VOTE_COOLDOWN),
+        ReputationError::ReputationVoteCooldown
+    );
+    voter_profile.last_reputation_vote_at = now;
+    voter_profile.credit(CAT_GOV, increment, now);
+    ctx.accounts.voter_realm_reputation.credit(CAT_GOV, increment, now);
 
     Ok(())
 }
\ No newline at end of file
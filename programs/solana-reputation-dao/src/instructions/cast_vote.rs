@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, transfer, Allocate, Assign, Transfer};
+use crate::state::{
+    delegation, ReputationProfile, GovernanceRealm, Proposal, ProposalStatus, VoteRecord, RealmReputation,
+    DelegationRecord, DelegationEdge, ProxyState,
+};
+use crate::utils::security;
+use crate::utils::constants::*;
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, has_one = realm)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, seeds = [b"reputation", voter.key().as_ref()], bump = voter_profile.bump)]
+    pub voter_profile: Account<'info, ReputationProfile>,
+    /// Voter's ledger, required when the realm uses local reputation
+    pub voter_realm_reputation: Option<Account<'info, RealmReputation>>,
+    /// Already present when a delegate carried the voter's power; voting directly takes it back
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// remaining_accounts: (DelegationRecord covering the proposal's category, delegator's
+// ReputationProfile, delegator's VoteRecord PDA) triples for the delegators
+// whose power this vote carries, each after its delegatee. Expired delegations
+// are skipped; revoke_delegation closes them.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>, vote_type: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &ctx.accounts.proposal;
+    require!(proposal.status == ProposalStatus::Active, ReputationError::ProposalNotActive);
+    require!(clock.unix_timestamp < proposal.voting_ends_at, ReputationError::VotingPeriodEnded);
+    require!(vote_type <= VOTE_ABSTAIN, ReputationError::InvalidVoteType);
+
+    let ledger = ctx.accounts.voter_realm_reputation.as_deref();
+    security::authorize_realm_action(&ctx.accounts.voter_profile, &ctx.accounts.realm, ledger, 0)?;
+
+    // A direct vote overrides the voter's own delegation. Delegations are
+    // global, so local-reputation realms weigh the ledger alone. Own and
+    // carried power both use the proposal's category score (the total for
+    // general proposals).
+    let category = proposal.category;
+    let own_weight = if ctx.accounts.realm.local_reputation_only {
+        require!(ctx.remaining_accounts.is_empty(), ReputationError::InvalidDelegationChain);
+        let ledger = ledger.ok_or(ReputationError::RealmReputationRequired)?;
+        ledger.score_history.weight_at(
+            proposal.created_at,
+            ledger.total_score,
+            &ledger.category_scores,
+            category,
+        )
+    } else {
+        let profile = &ctx.accounts.voter_profile;
+        profile.score_history.weight_at(
+            proposal.created_at,
+            profile.total_score,
+            &profile.category_scores,
+            category,
+        )
+    };
+
+    let proposal_key = proposal.key();
+    let voter = ctx.accounts.voter.key();
+    require!(ctx.remaining_accounts.len().is_multiple_of(3), ReputationError::InvalidDelegationChain);
+
+    let mut edges = Vec::new();
+    let mut proxied = Vec::new();
+    for triple in ctx.remaining_accounts.chunks(3) {
+        let delegation = Account::<DelegationRecord>::try_from(&triple[0])?;
+        require!(delegation.covers(category), ReputationError::InvalidDelegationChain);
+        if delegation.is_expired(clock.unix_timestamp) {
+            continue;
+        }
+        let delegator_profile = Account::<ReputationProfile>::try_from(&triple[1])?;
+        require_keys_eq!(delegator_profile.wallet, delegation.delegator, ReputationError::InvalidDelegationChain);
+        let power = delegation.voting_power(&delegator_profile, category, proposal.created_at);
+
+        let record_info = &triple[2];
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"vote", proposal_key.as_ref(), delegation.delegator.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(record_info.key(), expected, ReputationError::InvalidDelegationChain);
+        require!(record_info.is_writable, ReputationError::InvalidDelegationChain);
+        let state = if record_info.data_is_empty() {
+            ProxyState::NotVoted
+        } else {
+            let existing = Account::<VoteRecord>::try_from(record_info)?;
+            if existing.cast_by == existing.voter {
+                ProxyState::Direct
+            } else {
+                ProxyState::Proxied(existing.cast_by)
+            }
+        };
+
+        edges.push(DelegationEdge {
+            delegator: delegation.delegator,
+            delegatee: delegation.delegatee,
+            power,
+            state,
+        });
+        proxied.push((record_info, bump));
+    }
+    let carried = delegation::resolve_delegation_chain(&voter, &edges, ctx.accounts.realm.max_delegation_depth)?;
+    let weight = own_weight.saturating_add(carried);
+
+    let proposal = &mut ctx.accounts.proposal;
+    let system_program = ctx.accounts.system_program.to_account_info();
+    for (edge, (record_info, bump)) in edges.iter().zip(proxied) {
+        if edge.state == ProxyState::NotVoted {
+            // Same steps as Anchor's init, so lamports sent to the PDA in
+            // advance cannot block the record: top up, allocate, assign
+            let seeds: &[&[u8]] = &[b"vote", proposal_key.as_ref(), edge.delegator.as_ref(), &[bump]];
+            let shortfall = Rent::get()?
+                .minimum_balance(VoteRecord::LEN)
+                .saturating_sub(record_info.lamports());
+            if shortfall > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: ctx.accounts.voter.to_account_info(),
+                            to: record_info.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            allocate(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Allocate { account_to_allocate: record_info.clone() },
+                    &[seeds],
+                ),
+                VoteRecord::LEN as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Assign { account_to_assign: record_info.clone() },
+                    &[seeds],
+                ),
+                &crate::ID,
+            )?;
+            let record = VoteRecord {
+                proposal: proposal_key,
+                voter: edge.delegator,
+                vote_type,
+                weight: edge.power,
+                voted_at: clock.unix_timestamp,
+                cast_by: voter,
+                bump,
+            };
+            record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+            // A delegator whose power is carried counts as a voter once
+            proposal.voter_count = proposal.voter_count.saturating_add(1);
+        } else {
+            let mut record = Account::<VoteRecord>::try_from(record_info)?;
+            let tally = proposal.tally_mut(record.vote_type)?;
+            *tally = tally.saturating_sub(record.weight);
+            record.vote_type = vote_type;
+            record.weight = edge.power;
+            record.voted_at = clock.unix_timestamp;
+            record.cast_by = voter;
+            record.exit(&crate::ID)?;
+        }
+    }
+
+    // Voting directly after a delegate carried this power: pull it out of their
+    // tally. The voter was already counted when the delegate's vote created the record.
+    let record = &mut ctx.accounts.vote_record;
+    if record.voter != Pubkey::default() {
+        require!(record.cast_by != voter, ReputationError::AlreadyVoted);
+        let tally = proposal.tally_mut(record.vote_type)?;
+        *tally = tally.saturating_sub(record.weight);
+    } else {
+        proposal.voter_count = proposal.voter_count.saturating_add(1);
+    }
+
+    let tally = proposal.tally_mut(vote_type)?;
+    *tally = tally.saturating_add(weight);
+    let voter_profile = &mut ctx.accounts.voter_profile;
+    voter_profile.votes_cast = voter_profile.votes_cast.saturating_add(1);
+
+    record.proposal = proposal_key;
+    record.voter = voter;
+    record.vote_type = vote_type;
+    record.weight = own_weight;
+    record.voted_at = clock.unix_timestamp;
+    record.cast_by = voter;
+    record.bump = ctx.bumps.vote_record;
+
+    emit!(VoteCast {
+        proposal: proposal_key,
+        voter,
+        vote_type,
+        weight,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub vote_type: u8,
+    pub weight: u64,
+    pub timestamp: i64,
+}
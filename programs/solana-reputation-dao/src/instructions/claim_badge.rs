@@ -1,62 +1,398 @@
 use anchor_lang::prelude::*;
-use crate::state::{ReputationProfile, Badge, BadgeType, BadgeReceipt};
+use crate::state::{
+    ReputationProfile, Badge, BadgeType, BadgeReceipt, BadgeDefinition, BadgeCollection, GovernanceRealm,
+    RealmReputation,
+};
 use crate::errors::ReputationError;
-use crate::utils::constants::*;
+use crate::utils::badge_token;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
 
 #[derive(Accounts)]
 pub struct ClaimBadge<'info> {
     #[account(mut, seeds = [b"reputation", user.key().as_ref()], bump = profile.bump)]
     pub profile: Account<'info, ReputationProfile>,
-    #[account(init, payer = user, space = BadgeReceipt::LEN)]
+    #[account(
+        init,
+        payer = user,
+        space = BadgeReceipt::LEN,
+        seeds = [b"badge", user.key().as_ref(), badge_definition.key().as_ref()],
+        bump
+    )]
     pub badge_account: Account<'info, BadgeReceipt>,
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        mut,
+        has_one = realm,
+        seeds = [b"badge_def", realm.key().as_ref(), &badge_definition.definition_id.to_le_bytes()],
+        bump = badge_definition.bump
+    )]
+    pub badge_definition: Account<'info, BadgeDefinition>,
+    /// Issuer co-signature; omit when claiming with a merkle proof
+    pub issuer: Option<Signer<'info>>,
+    /// Overflow page, required once the inline badge slots are full
+    #[account(mut)]
+    pub badge_collection: Option<Box<Account<'info, BadgeCollection>>>,
+    /// Holder's ledger in `realm`, the only score the bonus lands in; opened
+    /// here when the holder never joined the realm
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = RealmReputation::LEN,
+        seeds = [b"realm_rep", realm.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub realm_reputation: Box<Account<'info, RealmReputation>>,
+    /// CHECK: soulbound Token-2022 mint, created here; pass all token accounts or none
+    #[account(mut, seeds = [b"badge_mint", badge_account.key().as_ref()], bump)]
+    pub badge_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: user's associated token account for badge_mint, created here
+    #[account(mut)]
+    pub holder_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: program PDA acting as mint authority and permanent delegate
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<UncheckedAccount<'info>>,
+    pub token_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ClaimBadge>, badge_type: u8, proof_hash: [u8; 32]) -> Result<()> {
+pub(crate) fn handler(
+    ctx: Context<ClaimBadge>,
+    proof_hash: [u8; 32],
+    merkle_proof: Vec<[u8; 32]>,
+) -> Result<()> {
     let clock = Clock::get()?;
-    let bt: BadgeType = BadgeType::try_from(badge_type).map_err(|_| error!(ReputationError::InvalidBadgeProof))?;
+    let definition = &ctx.accounts.badge_definition;
+    let bt: BadgeType =
+        BadgeType::try_from(definition.badge_type).map_err(|_| error!(ReputationError::InvalidBadgeProof))?;
+    let issuer_realm = ctx.accounts.realm.realm_id;
+    check_claimable(definition, &ctx.accounts.profile, issuer_realm, clock.unix_timestamp)?;
 
-    if ctx.accounts.profile.badges.iter().any(|b| b.badge_type == bt) {
-        return err!(ReputationError::BadgeAlreadyClaimed);
-    }
+    verify_issuance(
+        definition,
+        ctx.accounts.issuer.as_ref().map(|issuer| issuer.key()),
+        &ctx.accounts.user.key(),
+        &proof_hash,
+        &merkle_proof,
+    )?;
+
+    let category = definition.category as usize;
+    let definition_id = definition.definition_id;
+    let definition_key = definition.key();
+    let expires_at = definition.badge_expiry(clock.unix_timestamp);
+    let name = match definition.name() {
+        n if n.is_empty() => format!("{} Badge", bt.label()),
+        n => n,
+    };
+    let uri = definition.uri();
+
+    // Capped per realm, so the receipt keeps what actually landed
+    let ledger = &mut ctx.accounts.realm_reputation;
+    ledger.open(issuer_realm, ctx.accounts.user.key(), clock.unix_timestamp, ctx.bumps.realm_reputation);
+    let score_bonus = ledger.credit_badge(category, definition.score_bonus, clock.unix_timestamp);
 
     let receipt = &mut ctx.accounts.badge_account;
     receipt.owner = ctx.accounts.user.key();
-    receipt.badge_type = badge_type;
+    receipt.badge_type = bt as u8;
+    receipt.issuer_realm = issuer_realm;
+    receipt.definition = definition_key;
+    receipt.score_bonus = score_bonus;
+    receipt.category = category as u8;
     receipt.proof_hash = proof_hash;
     receipt.earned_at = clock.unix_timestamp;
-    receipt.bump = 0;
+    receipt.expires_at = expires_at;
+    receipt.lapsed = false;
+    receipt.token_mint = Pubkey::default();
+    receipt.bump = ctx.bumps.badge_account;
 
-    let profile = &mut ctx.accounts.profile;
-    if let Some(slot) = profile.badges.iter_mut().find(|b| matches!(b.badge_type, BadgeType::None)) {
-        *slot = Badge {
-            badge_type: bt,
-            earned_at: clock.unix_timestamp,
-            issuer_realm: Pubkey::default(),
-            metadata_hash: proof_hash,
-        };
-    } else {
-        let last = profile.badges.last_mut().unwrap();
-        *last = Badge {
-            badge_type: bt,
-            earned_at: clock.unix_timestamp,
-            issuer_realm: Pubkey::default(),
-            metadata_hash: proof_hash,
+    let badge = Badge {
+        badge_type: bt,
+        definition_id,
+        earned_at: clock.unix_timestamp,
+        issuer_realm,
+        expires_at,
+        metadata_hash: proof_hash,
+    };
+
+    store_badge(
+        &mut ctx.accounts.profile,
+        ctx.accounts.badge_collection.as_deref_mut().map(|c| &mut **c),
+        badge,
+        category,
+    )?;
+    let definition = &mut ctx.accounts.badge_definition;
+    definition.supply = definition.supply.saturating_add(1);
+
+    match (
+        ctx.accounts.badge_mint.as_ref(),
+        ctx.accounts.holder_token_account.as_ref(),
+        ctx.accounts.badge_authority.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.associated_token_program.as_ref(),
+    ) {
+        (Some(mint), Some(holder_token_account), Some(authority), Some(token_program), Some(ata_program)) => {
+            let receipt_key = ctx.accounts.badge_account.key();
+            let mint_bump = ctx.bumps.badge_mint;
+            let authority_bump = ctx.bumps.badge_authority;
+            let accounts = badge_token::BadgeTokenAccounts {
+                payer: ctx.accounts.user.as_ref(),
+                holder: ctx.accounts.user.as_ref(),
+                mint: mint.as_ref(),
+                holder_token_account: holder_token_account.as_ref(),
+                authority: authority.as_ref(),
+                token_program: token_program.as_ref(),
+                associated_token_program: ata_program.as_ref(),
+                system_program: ctx.accounts.system_program.as_ref(),
+            };
+            badge_token::mint_soulbound_badge(
+                &accounts,
+                &[b"badge_mint", receipt_key.as_ref(), &[mint_bump]],
+                &[b"badge_authority", &[authority_bump]],
+                name,
+                uri,
+                vec![
+                    ("badge_type".to_string(), bt.label().to_string()),
+                    ("issuer_realm".to_string(), issuer_realm.to_string()),
+                    ("definition".to_string(), definition_key.to_string()),
+                    ("metadata_hash".to_string(), badge_token::to_hex(&proof_hash)),
+                ],
+            )?;
+            ctx.accounts.badge_account.token_mint = mint.key();
+        }
+        (None, None, None, None, None) => {}
+        _ => return err!(ReputationError::InvalidBadgeToken),
+    }
+
+    Ok(())
+}
+
+// Sorted-pair keccak merkle proof, leaf = keccak(owner || proof_hash)
+fn verify_badge_merkle_proof(
+    root: &[u8; 32],
+    owner: &Pubkey,
+    proof_hash: &[u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    use anchor_lang::solana_program::keccak;
+    let mut node = keccak::hashv(&[owner.as_ref(), proof_hash]).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
         };
     }
+    node == *root
+}
 
-    profile.total_score = profile.total_score.saturating_add(BADGE_SCORE_BONUS);
-    let idx = match bt {
-        BadgeType::Developer => 0,
-        BadgeType::GovernanceParticipant => 1,
-        BadgeType::CommunityBuilder | BadgeType::Mentor | BadgeType::EarlyAdopter => 2,
-        BadgeType::Innovation | BadgeType::AIValidator => 3,
-        BadgeType::SecurityAuditor => 4,
-        BadgeType::CrossChainBridge | BadgeType::Custom | BadgeType::None => 2,
-    };
-    profile.category_scores[idx] = profile.category_scores[idx].saturating_add(BADGE_SCORE_BONUS);
+// Issuer co-signature or merkle proof against the definition
+pub(crate) fn verify_issuance(
+    definition: &BadgeDefinition,
+    issuer: Option<Pubkey>,
+    owner: &Pubkey,
+    proof_hash: &[u8; 32],
+    merkle_proof: &[[u8; 32]],
+) -> Result<()> {
+    let issued = issuer.is_some_and(|issuer| definition.issuer != Pubkey::default() && issuer == definition.issuer);
+    let proven = definition.merkle_root != [0u8; 32]
+        && verify_badge_merkle_proof(&definition.merkle_root, owner, proof_hash, merkle_proof);
+    require!(issued || proven, ReputationError::InvalidBadgeProof);
+    Ok(())
+}
 
+// Supply check on top of check_eligible, shared by every award path. Duplicates
+// need no scan: the ["badge", owner, definition] receipt is created with
+// `init`, so a second award of the same definition fails while the first
+// receipt exists, wherever its Badge is stored (inline or a collection page).
+pub(crate) fn check_claimable(
+    definition: &BadgeDefinition,
+    profile: &ReputationProfile,
+    issuer_realm: Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        definition.max_supply == 0 || definition.supply < definition.max_supply,
+        ReputationError::BadgeSupplyExhausted
+    );
+    check_eligible(definition, profile, issuer_realm, now)
+}
+
+// Definition expiry and eligibility rules, re-checked when a badge is renewed
+pub(crate) fn check_eligible(
+    definition: &BadgeDefinition,
+    profile: &ReputationProfile,
+    issuer_realm: Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        definition.expires_at == 0 || now <= definition.expires_at,
+        ReputationError::BadgeDefinitionExpired
+    );
+    let eligibility = definition.eligibility;
+    require!(
+        profile.total_score >= eligibility.min_total_score
+            && profile.category_scores[definition.category as usize] >= eligibility.min_category_score
+            && (!eligibility.members_only || profile.realm_memberships.contains(&issuer_realm)),
+        ReputationError::BadgeEligibilityNotMet
+    );
     Ok(())
-}
\ No newline at end of file
+}
+
+// Places the badge inline or in the overflow page. Realm admins choose who
+// issues their badges, so the bonus goes to the realm ledger, never here.
+pub(crate) fn store_badge(
+    profile: &mut ReputationProfile,
+    collection: Option<&mut BadgeCollection>,
+    badge: Badge,
+    category: usize,
+) -> Result<()> {
+    if let Some(slot) = profile.badges.iter_mut().find(|b| matches!(b.badge_type, BadgeType::None)) {
+        *slot = badge;
+    } else {
+        let collection = collection.ok_or(ReputationError::BadgeStorageFull)?;
+        require_keys_eq!(collection.owner, profile.wallet, ReputationError::BadgeStorageFull);
+        let slot = collection
+            .badges
+            .iter_mut()
+            .find(|b| matches!(b.badge_type, BadgeType::None))
+            .ok_or(ReputationError::BadgeStorageFull)?;
+        *slot = badge;
+    }
+
+    profile.badge_count = profile.badge_count.saturating_add(1);
+    profile.badge_category_counts[category] = profile.badge_category_counts[category].saturating_add(1);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::BadgeEligibility;
+    use crate::utils::constants::BADGES_PER_COLLECTION;
+
+    fn badge(definition_id: u16) -> Badge {
+        Badge {
+            badge_type: BadgeType::Developer,
+            definition_id,
+            issuer_realm: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    fn collection(owner: Pubkey) -> BadgeCollection {
+        BadgeCollection {
+            owner,
+            page: 0,
+            badges: [Badge::default(); BADGES_PER_COLLECTION],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn badges_fill_inline_slots_then_collection_pages() {
+        let mut profile = ReputationProfile { wallet: Pubkey::new_unique(), ..Default::default() };
+        for id in 0..profile.badges.len() as u16 {
+            store_badge(&mut profile, None, badge(id), 0).unwrap();
+        }
+        assert_eq!(profile.badge_count, 10);
+
+        // Inline slots are full: an overflow page is now required
+        assert_eq!(
+            store_badge(&mut profile, None, badge(10), 0).unwrap_err(),
+            error!(ReputationError::BadgeStorageFull)
+        );
+        let mut page = collection(profile.wallet);
+        store_badge(&mut profile, Some(&mut page), badge(10), 1).unwrap();
+        assert_eq!(page.badges[0].definition_id, 10);
+        assert_eq!(profile.badge_count, 11);
+        assert_eq!(profile.badge_category_counts[0..2], [10, 1]);
+        // Bonuses are credited to the realm ledger by the caller, not here
+        assert_eq!(profile.total_score, 0);
+    }
+
+    #[test]
+    fn badges_never_land_in_someone_elses_collection() {
+        let mut profile = ReputationProfile { wallet: Pubkey::new_unique(), ..Default::default() };
+        profile.badges = [badge(0); 10];
+        let mut foreign = collection(Pubkey::new_unique());
+        assert_eq!(
+            store_badge(&mut profile, Some(&mut foreign), badge(1), 0).unwrap_err(),
+            error!(ReputationError::BadgeStorageFull)
+        );
+        assert!(foreign.badges[0].badge_type == BadgeType::None);
+    }
+
+    fn members_only_definition(realm: Pubkey) -> BadgeDefinition {
+        BadgeDefinition {
+            realm,
+            definition_id: 0,
+            badge_type: BadgeType::Developer as u8,
+            name: [0; 32],
+            uri: [0; 128],
+            score_bonus: 25,
+            category: 0,
+            max_supply: 1,
+            supply: 1,
+            expires_at: 0,
+            validity_seconds: 86_400,
+            eligibility: BadgeEligibility { min_total_score: 50, min_category_score: 0, members_only: true },
+            criteria: Default::default(),
+            issuer: Pubkey::new_unique(),
+            merkle_root: [0; 32],
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn renewal_rechecks_eligibility_but_not_supply() {
+        let realm = Pubkey::new_unique();
+        let definition = members_only_definition(realm);
+        let mut profile = ReputationProfile { wallet: Pubkey::new_unique(), total_score: 60, ..Default::default() };
+        profile.realm_memberships[0] = realm;
+
+        // The holder's own badge already counts against the supply
+        assert_eq!(
+            check_claimable(&definition, &profile, realm, 0).unwrap_err(),
+            error!(ReputationError::BadgeSupplyExhausted)
+        );
+        check_eligible(&definition, &profile, realm, 0).unwrap();
+
+        // Leaving the realm or losing reputation blocks the renewal
+        profile.realm_memberships[0] = Pubkey::default();
+        assert_eq!(
+            check_eligible(&definition, &profile, realm, 0).unwrap_err(),
+            error!(ReputationError::BadgeEligibilityNotMet)
+        );
+        profile.realm_memberships[0] = realm;
+        profile.total_score = 40;
+        assert_eq!(
+            check_eligible(&definition, &profile, realm, 0).unwrap_err(),
+            error!(ReputationError::BadgeEligibilityNotMet)
+        );
+    }
+
+    #[test]
+    fn badge_merkle_proof_binds_owner_and_proof() {
+        use anchor_lang::solana_program::keccak;
+        let hash_pair = |a: [u8; 32], b: [u8; 32]| {
+            let (l, r) = if a <= b { (a, b) } else { (b, a) };
+            keccak::hashv(&[&l, &r]).to_bytes()
+        };
+
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let proof_hash = [7u8; 32];
+        let leaf_alice = keccak::hashv(&[alice.as_ref(), &proof_hash]).to_bytes();
+        let leaf_bob = keccak::hashv(&[bob.as_ref(), &proof_hash]).to_bytes();
+        let root = hash_pair(leaf_alice, leaf_bob);
+
+        assert!(verify_badge_merkle_proof(&root, &alice, &proof_hash, &[leaf_bob]));
+        assert!(verify_badge_merkle_proof(&root, &bob, &proof_hash, &[leaf_alice]));
+        assert!(!verify_badge_merkle_proof(&root, &Pubkey::new_unique(), &proof_hash, &[leaf_bob]));
+        assert!(!verify_badge_merkle_proof(&root, &alice, &[8u8; 32], &[leaf_bob]));
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::InteractionEvent;
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct CloseInteractionEvent<'info> {
+    #[account(
+        mut,
+        close = from_user,
+        seeds = [
+            b"interaction",
+            interaction_event.from.as_ref(),
+            interaction_event.to.as_ref(),
+            &interaction_event.nonce.to_le_bytes(),
+        ],
+        bump = interaction_event.bump
+    )]
+    pub interaction_event: Account<'info, InteractionEvent>,
+    /// Original payer of the event's rent
+    #[account(mut, address = interaction_event.from)]
+    pub from_user: SystemAccount<'info>,
+    pub caller: Signer<'info>,
+}
+
+// Scores applied by the interaction are untouched; the InteractionRecorded
+// log remains the permanent record once the account is gone. Later retention
+// changes in the realm never move an already recorded expiry.
+pub(crate) fn handler(ctx: Context<CloseInteractionEvent>) -> Result<()> {
+    let clock = Clock::get()?;
+    let event = &ctx.accounts.interaction_event;
+    require!(event.open_disputes == 0 || event.reversed, ReputationError::DisputeOpen);
+    require!(clock.unix_timestamp >= event.expires_at, ReputationError::RetentionPeriodActive);
+
+    Ok(())
+}
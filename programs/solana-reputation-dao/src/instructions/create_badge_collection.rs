@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, BadgeCollection};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct CreateBadgeCollection<'info> {
+    #[account(mut, seeds = [b"reputation", user.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    #[account(
+        init,
+        payer = user,
+        space = BadgeCollection::LEN,
+        seeds = [b"badge_collection", user.key().as_ref(), &profile.badge_pages.to_le_bytes()],
+        bump
+    )]
+    pub badge_collection: Box<Account<'info, BadgeCollection>>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<CreateBadgeCollection>) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let collection = &mut ctx.accounts.badge_collection;
+    collection.owner = ctx.accounts.user.key();
+    collection.page = profile.badge_pages;
+    collection.badges = Default::default();
+    collection.bump = ctx.bumps.badge_collection;
+
+    profile.badge_pages = profile.badge_pages.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, BadgeDefinition, BadgeDefinitionArgs, BadgeType};
+use crate::errors::ReputationError;
+use crate::utils::constants::*;
+
+#[derive(Accounts)]
+pub struct CreateBadgeDefinition<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        init,
+        payer = admin,
+        space = BadgeDefinition::LEN,
+        seeds = [b"badge_def", realm.key().as_ref(), &realm.badge_definition_count.to_le_bytes()],
+        bump
+    )]
+    pub badge_definition: Account<'info, BadgeDefinition>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// remaining_accounts: co-signing realm admins, enough with `admin` to reach admin_threshold
+pub(crate) fn handler(ctx: Context<CreateBadgeDefinition>, args: BadgeDefinitionArgs) -> Result<()> {
+    ctx.accounts.realm.check_admin_quorum(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+    let bt = BadgeType::try_from(args.badge_type).map_err(|_| error!(ReputationError::InvalidBadgeProof))?;
+    require!(bt != BadgeType::None, ReputationError::InvalidBadgeProof);
+    require!(
+        args.issuer != Pubkey::default() || args.merkle_root != [0u8; 32] || args.criteria.auto_award,
+        ReputationError::BadgeDefinitionUnverifiable
+    );
+    require!(
+        args.name.len() <= MAX_BADGE_NAME_LEN && args.uri.len() <= MAX_BADGE_URI_LEN,
+        ReputationError::BadgeMetadataTooLong
+    );
+    require!((args.category as usize) <= CAT_SEC, ReputationError::InvalidBadgeCategory);
+    require!(args.score_bonus <= MAX_BADGE_SCORE_BONUS, ReputationError::BadgeScoreBonusTooHigh);
+    require!(args.validity_seconds >= 0, ReputationError::InvalidBadgeValidity);
+    require!(
+        !args.criteria.auto_award || args.criteria.requires_earned_reputation(),
+        ReputationError::AutoAwardCriteriaTooWeak
+    );
+
+    let clock = Clock::get()?;
+    let realm = &mut ctx.accounts.realm;
+    let definition = &mut ctx.accounts.badge_definition;
+    definition.realm = realm.realm_id;
+    definition.definition_id = realm.badge_definition_count;
+    definition.badge_type = args.badge_type;
+    let mut name_buf = [0u8; MAX_BADGE_NAME_LEN];
+    name_buf[..args.name.len()].copy_from_slice(args.name.as_bytes());
+    definition.name = name_buf;
+    let mut uri_buf = [0u8; MAX_BADGE_URI_LEN];
+    uri_buf[..args.uri.len()].copy_from_slice(args.uri.as_bytes());
+    definition.uri = uri_buf;
+    definition.score_bonus = args.score_bonus;
+    definition.category = args.category;
+    definition.max_supply = args.max_supply;
+    definition.supply = 0;
+    definition.expires_at = args.expires_at;
+    definition.validity_seconds = args.validity_seconds;
+    definition.eligibility = args.eligibility;
+    definition.criteria = args.criteria;
+    definition.issuer = args.issuer;
+    definition.merkle_root = args.merkle_root;
+    definition.created_at = clock.unix_timestamp;
+    definition.bump = ctx.bumps.badge_definition;
+
+    realm.badge_definition_count = realm
+        .badge_definition_count
+        .checked_add(1)
+        .ok_or(ReputationError::MathOverflow)?;
+
+    Ok(())
+}
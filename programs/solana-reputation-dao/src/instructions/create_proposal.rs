@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, GovernanceRealm, Proposal, ProposalStatus, RealmReputation};
+use crate::utils::{reputation_math, security};
+use crate::utils::constants::*;
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(seeds = [b"reputation", proposer.key().as_ref()], bump = proposer_profile.bump)]
+    pub proposer_profile: Account<'info, ReputationProfile>,
+    /// Proposer's ledger, required when the realm uses local reputation
+    pub proposer_realm_reputation: Option<Account<'info, RealmReputation>>,
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [b"proposal", realm.key().as_ref(), &realm.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<CreateProposal>, title: String, description: String, category: u8) -> Result<()> {
+    require!(title.len() <= MAX_PROPOSAL_TITLE_LEN, ReputationError::ProposalTitleTooLong);
+    require!(description.len() <= 256, ReputationError::MetadataTooLong);
+    require!(
+        (category as usize) <= CAT_SEC || category == PROPOSAL_CATEGORY_GENERAL,
+        ReputationError::InvalidProposalCategory
+    );
+
+    security::authorize_realm_action(
+        &ctx.accounts.proposer_profile,
+        &ctx.accounts.realm,
+        ctx.accounts.proposer_realm_reputation.as_deref(),
+        1,
+    )?;
+
+    let clock = Clock::get()?;
+    let realm_key = ctx.accounts.realm.key();
+    let realm = &mut ctx.accounts.realm;
+    let proposal_id = realm.proposal_count;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.realm = realm_key;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.proposal_id = proposal_id;
+    let mut title_buf = [0u8; MAX_PROPOSAL_TITLE_LEN];
+    title_buf[..title.len()].copy_from_slice(title.as_bytes());
+    proposal.title = title_buf;
+    proposal.description_hash = reputation_math::hash_metadata(&description);
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.abstain_votes = 0;
+    proposal.voter_count = 0;
+    proposal.status = ProposalStatus::Active;
+    proposal.category = category;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.voting_ends_at = clock
+        .unix_timestamp
+        .checked_add(realm.voting_period_seconds as i64)
+        .ok_or(ReputationError::MathOverflow)?;
+    proposal.bump = ctx.bumps.proposal;
+
+    realm.proposal_count = realm.proposal_count.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+    realm.active_proposals = realm.active_proposals.saturating_add(1);
+
+    emit!(ProposalCreated {
+        realm: realm_key,
+        proposal: proposal.key(),
+        proposer: proposal.proposer,
+        proposal_id,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub realm: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub proposal_id: u64,
+    pub voting_ends_at: i64,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{GovernanceRealm, ReputationAlgorithm};
 use crate::errors::ReputationError;
+use crate::utils::constants::*;
 
 #[derive(Accounts)]
 #[instruction(realm_name: String, _algorithm_weights: [u16; 5])]
@@ -18,20 +19,20 @@ pub struct CreateRealm<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<CreateRealm>,
     realm_name: String,
     algorithm_weights: [u16; 5],
 ) -> Result<()> {
     require!(realm_name.len() <= 32, ReputationError::RealmNameTooLong);
 
-    let total_weight: u64 = algorithm_weights.iter().map(|&x| x as u64).sum();
-    require!(total_weight > 0, ReputationError::InvalidAlgorithmWeights);
+    ReputationAlgorithm::validate_weights(&algorithm_weights)?;
 
     let clock = Clock::get()?;
+    let realm_key = ctx.accounts.realm.key();
     let realm = &mut ctx.accounts.realm;
 
-    realm.realm_id = ctx.accounts.realm.key();
+    realm.realm_id = realm_key;
     let mut name_buf = [0u8; 32];
     let name_bytes = realm_name.as_bytes();
     name_buf[..name_bytes.len()].copy_from_slice(name_bytes);
@@ -56,6 +57,16 @@ pub fn handler(
     realm.cross_realm_enabled = true;
     realm.ai_moderation_enabled = false;
     realm.created_at = clock.unix_timestamp;
+    realm.proposal_count = 0;
+    realm.interaction_retention_seconds = DEFAULT_INTERACTION_RETENTION;
+    realm.badge_definition_count = 0;
+    realm.max_bridged_reputation = DEFAULT_MAX_BRIDGED_REPUTATION;
+    realm.local_reputation_only = false;
+    realm.attestation_authority = Pubkey::default();
+    realm.max_delegation_depth = DEFAULT_DELEGATION_DEPTH;
+    realm.admin_threshold = 1;
+    realm.admin_action_count = 0;
+    realm.admin_epoch = 0;
     realm.bump = ctx.bumps.realm;
 
     Ok(())
@@ -1,36 +1,149 @@
 use anchor_lang::prelude::*;
-use crate::state::ReputationProfile;
+use crate::state::{delegation, ReputationProfile, DelegationRecord};
+use super::revoke_delegation::DelegationRevoked;
 use crate::errors::ReputationError;
+use crate::utils::constants::{DELEGATION_MASK_ALL, MAX_DELEGATION_DEPTH};
 
 #[derive(Accounts)]
+#[instruction(delegate_to: Pubkey)]
 pub struct DelegateReputation<'info> {
     #[account(mut, seeds = [b"reputation", delegator.key().as_ref()], bump = delegator_profile.bump)]
     pub delegator_profile: Account<'info, ReputationProfile>,
-    #[account(mut)]
+    #[account(seeds = [b"reputation", delegate_to.as_ref()], bump = delegatee_profile.bump)]
     pub delegatee_profile: Account<'info, ReputationProfile>,
+    /// One per (delegator, delegatee); delegating to the same delegatee again replaces it
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = DelegationRecord::LEN,
+        seeds = [b"delegation", delegator.key().as_ref(), delegate_to.as_ref()],
+        bump
+    )]
+    pub delegation_record: Account<'info, DelegationRecord>,
+    /// Delegation being moved to the new delegatee, closed in the same
+    /// instruction so its categories change hands atomically
+    #[account(
+        mut,
+        close = delegator,
+        has_one = delegator,
+        constraint = previous_record.key() != delegation_record.key() @ ReputationError::InvalidDelegationChain
+    )]
+    pub previous_record: Option<Account<'info, DelegationRecord>>,
     #[account(mut)]
     pub delegator: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<DelegateReputation>,
+// remaining_accounts: the DelegationRecords that carry any of the mask's
+// categories onward from the delegatee, and the ReputationProfile of each
+// wallet they point to, in any order. Each category is followed separately; a
+// profile without it in delegated_categories ends that chain, so only
+// delegations that exist need to be passed.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DelegateReputation<'info>>,
     delegate_to: Pubkey,
     weight_percentage: u8,
+    category_mask: u8,
+    expires_at: i64,
 ) -> Result<()> {
     require!(weight_percentage > 0 && weight_percentage <= 100, ReputationError::DelegationTooHigh);
+    require!(
+        category_mask != 0 && category_mask & !DELEGATION_MASK_ALL == 0,
+        ReputationError::InvalidCategoryMask
+    );
+    let clock = Clock::get()?;
+    require!(expires_at == 0 || expires_at > clock.unix_timestamp, ReputationError::DelegationExpired);
+
+    let delegator = ctx.accounts.delegator_profile.wallet;
+    require!(delegator != delegate_to, ReputationError::SelfDelegationForbidden);
+
+    // Categories held by the records this instruction replaces are free again
+    let mut held = ctx.accounts.delegator_profile.delegated_categories;
+    if let Some(previous) = ctx.accounts.previous_record.as_ref() {
+        held &= !previous.category_mask;
+    }
+    let record = &ctx.accounts.delegation_record;
+    if record.delegator != Pubkey::default() {
+        held &= !record.category_mask;
+    }
+    require!(held & category_mask == 0, ReputationError::DelegationCategoryTaken);
+
+    let mut records = Vec::new();
+    let mut profiles = Vec::new();
+    for info in ctx.remaining_accounts {
+        match Account::<DelegationRecord>::try_from(info) {
+            Ok(hop) => records.push(hop),
+            Err(_) => profiles.push(Account::<ReputationProfile>::try_from(info)?),
+        }
+    }
+    for bit in (0..8).map(|shift| 1u8 << shift).filter(|bit| category_mask & bit != 0) {
+        let mut path = Vec::new();
+        let mut wallet = delegate_to;
+        let mut onward = ctx.accounts.delegatee_profile.delegated_categories;
+        while onward & bit != 0 && path.len() < MAX_DELEGATION_DEPTH as usize {
+            let hop = records
+                .iter()
+                .find(|hop| hop.delegator == wallet && hop.category_mask & bit != 0)
+                .ok_or(ReputationError::InvalidDelegationChain)?;
+            if hop.is_expired(clock.unix_timestamp) {
+                break;
+            }
+            wallet = hop.delegatee;
+            path.push(wallet);
+            if wallet == delegator {
+                break;
+            }
+            onward = profiles
+                .iter()
+                .find(|profile| profile.wallet == wallet)
+                .ok_or(ReputationError::InvalidDelegationChain)?
+                .delegated_categories;
+        }
+        delegation::check_delegation_path(&delegator, &path)?;
+    }
 
-    let delegator_profile = &mut ctx.accounts.delegator_profile;
-    require!(delegator_profile.wallet != delegate_to, ReputationError::SelfDelegationForbidden);
-    require!(ctx.accounts.delegatee_profile.wallet == delegate_to, ReputationError::ProfileNotInitialized);
+    if let Some(previous) = ctx.accounts.previous_record.as_ref() {
+        emit!(DelegationRevoked {
+            delegator,
+            delegatee: previous.delegatee,
+            category_mask: previous.category_mask,
+            revoked_by: delegator,
+        });
+    }
+    ctx.accounts.delegator_profile.delegated_categories = held | category_mask;
 
-    let power = (delegator_profile.total_score as u128 * weight_percentage as u128 / 100) as u64;
+    let record = &mut ctx.accounts.delegation_record;
+    let previous_delegatee = match ctx.accounts.previous_record.as_ref() {
+        Some(previous) => previous.delegatee,
+        None => record.delegatee,
+    };
 
-    let prev = delegator_profile.delegated_power;
-    delegator_profile.delegated_power = power;
+    record.delegator = delegator;
+    record.delegatee = delegate_to;
+    record.category_mask = category_mask;
+    record.weight_percentage = weight_percentage;
+    record.created_at = clock.unix_timestamp;
+    record.expires_at = expires_at;
+    record.bump = ctx.bumps.delegation_record;
 
-    let delegatee_profile = &mut ctx.accounts.delegatee_profile;
-    delegatee_profile.delegation_received =
-        delegatee_profile.delegation_received.saturating_add(power.saturating_sub(prev));
+    emit!(DelegationChanged {
+        delegator,
+        delegatee: delegate_to,
+        previous_delegatee,
+        category_mask,
+        weight_percentage,
+        expires_at,
+    });
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct DelegationChanged {
+    pub delegator: Pubkey,
+    pub delegatee: Pubkey,
+    pub previous_delegatee: Pubkey,
+    pub category_mask: u8,
+    pub weight_percentage: u8,
+    pub expires_at: i64,
+}
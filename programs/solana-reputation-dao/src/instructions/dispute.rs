@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, GovernanceRealm, InteractionEvent, Dispute, DisputeStatus, RealmReputation};
+use crate::utils::reputation_math;
+use crate::utils::constants::*;
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub interaction_event: Account<'info, InteractionEvent>,
+    #[account(
+        init,
+        payer = opener,
+        space = Dispute::LEN,
+        seeds = [b"dispute", interaction_event.key().as_ref(), realm.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub realm: Account<'info, GovernanceRealm>,
+    /// Realm-less events are arbitrated by a realm the receiver belongs to
+    #[account(seeds = [b"reputation", interaction_event.to.as_ref()], bump = receiver_profile.bump)]
+    pub receiver_profile: Account<'info, ReputationProfile>,
+    #[account(seeds = [b"reputation", opener.key().as_ref()], bump = opener_profile.bump)]
+    pub opener_profile: Account<'info, ReputationProfile>,
+    #[account(mut)]
+    pub opener: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut, has_one = realm, has_one = interaction_event)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub interaction_event: Account<'info, InteractionEvent>,
+    pub realm: Account<'info, GovernanceRealm>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReverseInteraction<'info> {
+    #[account(mut, has_one = interaction_event)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub interaction_event: Account<'info, InteractionEvent>,
+    #[account(
+        mut,
+        seeds = [b"reputation", interaction_event.from.as_ref()],
+        bump = from_profile.bump
+    )]
+    pub from_profile: Account<'info, ReputationProfile>,
+    #[account(
+        mut,
+        seeds = [b"reputation", interaction_event.to.as_ref()],
+        bump = to_profile.bump
+    )]
+    pub to_profile: Account<'info, ReputationProfile>,
+    /// Receiver's ledger in the event's realm, debited by what the event
+    /// credited it; omitted for realm-less events
+    #[account(
+        mut,
+        seeds = [b"realm_rep", interaction_event.realm.as_ref(), interaction_event.to.as_ref()],
+        bump = to_realm_reputation.bump
+    )]
+    pub to_realm_reputation: Option<Account<'info, RealmReputation>>,
+    pub caller: Signer<'info>,
+}
+
+pub(crate) fn open_handler(ctx: Context<OpenDispute>, reason: String) -> Result<()> {
+    require!(reason.len() <= 256, ReputationError::MetadataTooLong);
+
+    let event = &ctx.accounts.interaction_event;
+    let realm = &ctx.accounts.realm;
+    let opener = ctx.accounts.opener.key();
+    require!(!event.reversed, ReputationError::InvalidDisputeState);
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < event.timestamp.saturating_add(DISPUTE_WINDOW),
+        ReputationError::DisputeWindowClosed
+    );
+    // Only the realm the interaction was recorded under may arbitrate it. A
+    // realm-less event may be disputed in any realm the receiver belongs to,
+    // each under its own dispute PDA, so a realm the receiver runs cannot
+    // dismiss the event on everyone else's behalf.
+    if event.realm == Pubkey::default() {
+        require!(
+            ctx.accounts.receiver_profile.realm_memberships.contains(&realm.realm_id),
+            ReputationError::DisputeRealmRequired
+        );
+    } else {
+        require_keys_eq!(event.realm, realm.realm_id, ReputationError::RealmMismatch);
+    }
+
+    // Receiver, a realm admin, or a high-reputation realm member
+    let profile = &ctx.accounts.opener_profile;
+    let is_receiver = opener == event.to;
+    let is_admin = realm.admin_wallets.contains(&opener);
+    let is_trusted_member = profile.realm_memberships.contains(&realm.realm_id)
+        && profile.total_score >= DISPUTE_MIN_REPUTATION;
+    require!(is_receiver || is_admin || is_trusted_member, ReputationError::DisputeNotAuthorized);
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.interaction_event = event.key();
+    dispute.realm = realm.realm_id;
+    dispute.opened_by = opener;
+    dispute.reason_hash = reputation_math::hash_metadata(&reason);
+    dispute.status = DisputeStatus::Open;
+    dispute.trust_slash = 0;
+    dispute.opened_at = clock.unix_timestamp;
+    dispute.resolved_at = 0;
+    dispute.bump = ctx.bumps.dispute;
+
+    let event = &mut ctx.accounts.interaction_event;
+    event.open_disputes = event.open_disputes.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+
+    emit!(DisputeOpened {
+        dispute: dispute.key(),
+        interaction_event: dispute.interaction_event,
+        opened_by: opener,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// remaining_accounts: co-signing realm admins, enough with `admin` to reach admin_threshold
+pub(crate) fn resolve_handler(ctx: Context<ResolveDispute>, uphold: bool, trust_slash: u64) -> Result<()> {
+    ctx.accounts.realm.check_admin_quorum(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+    require!(trust_slash <= MAX_TRUST_SLASH, ReputationError::TrustSlashTooHigh);
+
+    let clock = Clock::get()?;
+    let dispute = &mut ctx.accounts.dispute;
+    require!(dispute.status == DisputeStatus::Open, ReputationError::InvalidDisputeState);
+
+    if uphold {
+        dispute.status = DisputeStatus::Upheld;
+        dispute.trust_slash = trust_slash;
+    } else {
+        dispute.status = DisputeStatus::Dismissed;
+        let event = &mut ctx.accounts.interaction_event;
+        event.open_disputes = event.open_disputes.saturating_sub(1);
+    }
+    dispute.resolved_at = clock.unix_timestamp;
+
+    emit!(DisputeResolved {
+        dispute: dispute.key(),
+        upheld: uphold,
+        trust_slash: dispute.trust_slash,
+        resolved_by: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn reverse_handler(ctx: Context<ReverseInteraction>) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    require!(dispute.status == DisputeStatus::Upheld, ReputationError::InvalidDisputeState);
+
+    let event = &mut ctx.accounts.interaction_event;
+    // Several realms may uphold a realm-less event; it is only reversed once
+    require!(!event.reversed, ReputationError::InvalidDisputeState);
+    let delta = event.reputation_delta;
+    let cat_index = reputation_math::interaction_category(event.interaction_type);
+
+    let to_profile = &mut ctx.accounts.to_profile;
+    to_profile.total_score = to_profile.total_score.saturating_sub(delta);
+    to_profile.category_scores[cat_index] = to_profile.category_scores[cat_index].saturating_sub(delta);
+    match ctx.accounts.to_realm_reputation.as_mut() {
+        Some(ledger) => ledger.debit(cat_index, event.realm_delta),
+        None => require!(event.realm == Pubkey::default(), ReputationError::RealmReputationRequired),
+    }
+
+    let from_profile = &mut ctx.accounts.from_profile;
+    from_profile.trust_multiplier = from_profile.trust_multiplier.saturating_sub(dispute.trust_slash);
+
+    event.reversed = true;
+    dispute.status = DisputeStatus::Reversed;
+
+    emit!(InteractionReversed {
+        interaction_event: event.key(),
+        from: event.from,
+        to: event.to,
+        reputation_delta: delta,
+        realm_delta: event.realm_delta,
+        trust_slash: dispute.trust_slash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dispute: Pubkey,
+    pub interaction_event: Pubkey,
+    pub opened_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute: Pubkey,
+    pub upheld: bool,
+    pub trust_slash: u64,
+    pub resolved_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InteractionReversed {
+    pub interaction_event: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub reputation_delta: u64,
+    pub realm_delta: u64,
+    pub trust_slash: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, BadgeReceipt, RealmReputation};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct ExpireBadges<'info> {
+    #[account(seeds = [b"reputation", profile.wallet.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    pub caller: Signer<'info>,
+}
+
+// Permissionless crank: pass (expired BadgeReceipt, owner's ledger in the
+// issuing realm) pairs as writable remaining accounts
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireBadges<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let profile = &ctx.accounts.profile;
+    require!(ctx.remaining_accounts.len().is_multiple_of(2), ReputationError::BadgeNotFound);
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        require!(pair[0].is_writable && pair[1].is_writable, ReputationError::BadgeNotFound);
+        let mut receipt = Account::<BadgeReceipt>::try_from(&pair[0])?;
+        let mut ledger = Account::<RealmReputation>::try_from(&pair[1])?;
+        require_keys_eq!(receipt.owner, profile.wallet, ReputationError::BadgeNotFound);
+        require!(
+            !receipt.lapsed && receipt.expires_at != 0 && now > receipt.expires_at,
+            ReputationError::BadgeNotExpired
+        );
+
+        ledger.ensure_for(&receipt.issuer_realm, &receipt.owner)?;
+
+        ledger.debit_badge(receipt.category as usize, receipt.score_bonus);
+        receipt.lapsed = true;
+        receipt.exit(&crate::ID)?;
+        ledger.exit(&crate::ID)?;
+
+        emit!(BadgeExpired {
+            owner: receipt.owner,
+            definition: receipt.definition,
+            expired_at: receipt.expires_at,
+            score_removed: receipt.score_bonus,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeExpired {
+    pub owner: Pubkey,
+    pub definition: Pubkey,
+    pub expired_at: i64,
+    pub score_removed: u64,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, Proposal, ProposalStatus};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, has_one = realm)]
+    pub proposal: Account<'info, Proposal>,
+    pub caller: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.status == ProposalStatus::Active, ReputationError::ProposalNotActive);
+    require!(clock.unix_timestamp >= proposal.voting_ends_at, ReputationError::VotingPeriodActive);
+
+    // Abstentions are recorded for turnout but never tip the outcome
+    proposal.status = if proposal.yes_votes > proposal.no_votes {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Rejected
+    };
+
+    let realm = &mut ctx.accounts.realm;
+    realm.active_proposals = realm.active_proposals.saturating_sub(1);
+
+    emit!(ProposalFinalized {
+        realm: realm.key(),
+        proposal: proposal.key(),
+        passed: proposal.status == ProposalStatus::Passed,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProposalFinalized {
+    pub realm: Pubkey,
+    pub proposal: Pubkey,
+    pub passed: bool,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub timestamp: i64,
+}
@@ -17,8 +17,8 @@ pub struct InitializeProfile<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<InitializeProfile>) -> Result<()> {
-    let _clock = Clock::get()?; // placeholder if you need created_at later
+pub(crate) fn handler(ctx: Context<InitializeProfile>) -> Result<()> {
+    let clock = Clock::get()?;
     let profile = &mut ctx.accounts.profile;
 
     profile.wallet = ctx.accounts.user.key();
@@ -26,14 +26,24 @@ pub fn handler(ctx: Context<InitializeProfile>) -> Result<()> {
     profile.category_scores = [0u64; 5];
     profile.interaction_count = 0;
     profile.badges = [Default::default(); MAX_BADGES as usize];
-    profile.trust_multiplier = DEFAULT_TRUST_MULTIPLIER as u64; // 100 = 1.00x
+    profile.trust_multiplier = DEFAULT_TRUST_MULTIPLIER; // 100 = 1.00x
     profile.last_activity = 0;
     profile.reputation_decay_rate = DEFAULT_DECAY_RATE;
-    profile.delegated_power = 0;
-    profile.delegation_received = 0;
     profile.realm_memberships = [Pubkey::default(); 5];
     profile.ai_validation_score = 500;
     profile.cross_dao_reputation = 0;
+    profile.last_decay_at = clock.unix_timestamp;
+    profile.day_start = 0;
+    profile.interactions_in_window = 0;
+    profile.last_sent_at = 0;
+    profile.interaction_nonce = 0;
+    profile.badge_count = 0;
+    profile.badge_pages = 0;
+    profile.badge_category_counts = [0u16; 5];
+    profile.votes_cast = 0;
+    profile.score_history = Default::default();
+    profile.last_reputation_vote_at = 0;
+    profile.delegated_categories = 0;
     profile.bump = ctx.bumps.profile;
 
     Ok(())
@@ -1,17 +1,58 @@
+
+pub mod admin_actions;
+pub mod apply_decay;
+pub mod award_earned_badge;
 pub mod bridge_reputation;
 pub mod cast_reputation_vote;
+pub mod cast_vote;
 pub mod claim_badge;
+pub mod close_interaction_event;
+pub mod create_badge_collection;
+pub mod create_badge_definition;
+pub mod create_proposal;
 pub mod create_realm;
 pub mod delegate_reputation;
+pub mod dispute;
+pub mod expire_badges;
+pub mod finalize_proposal;
 pub mod initialize_profile;
+pub mod realm_membership;
 pub mod record_interaction;
+pub mod renew_badge;
+pub mod reputation_attestation;
+pub mod revoke_badge;
+pub mod revoke_delegation;
+pub mod set_bridge_policy;
+pub mod set_delegation_depth;
+pub mod set_interaction_retention;
+pub mod set_reputation_scope;
 pub mod update_algorithm;
 
+pub use admin_actions::*;
+pub use apply_decay::*;
+pub use award_earned_badge::*;
 pub use bridge_reputation::*;
 pub use cast_reputation_vote::*;
+pub use cast_vote::*;
 pub use claim_badge::*;
+pub use close_interaction_event::*;
+pub use create_badge_collection::*;
+pub use create_badge_definition::*;
+pub use create_proposal::*;
 pub use create_realm::*;
 pub use delegate_reputation::*;
+pub use dispute::*;
+pub use expire_badges::*;
+pub use finalize_proposal::*;
 pub use initialize_profile::*;
+pub use realm_membership::*;
 pub use record_interaction::*;
+pub use renew_badge::*;
+pub use reputation_attestation::*;
+pub use revoke_badge::*;
+pub use revoke_delegation::*;
+pub use set_bridge_policy::*;
+pub use set_delegation_depth::*;
+pub use set_interaction_retention::*;
+pub use set_reputation_scope::*;
 pub use update_algorithm::*;
\ No newline at end of file
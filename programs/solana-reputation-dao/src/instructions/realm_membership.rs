@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct JoinRealm<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, seeds = [b"reputation", member.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = RealmReputation::LEN,
+        seeds = [b"realm_rep", realm.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub realm_reputation: Account<'info, RealmReputation>,
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveRealm<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, seeds = [b"reputation", member.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMember<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(mut, seeds = [b"reputation", member.key().as_ref()], bump = member_profile.bump)]
+    pub member_profile: Account<'info, ReputationProfile>,
+    /// CHECK: validated by member_profile seeds vs member
+    pub member: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn join_handler(ctx: Context<JoinRealm>) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    let profile = &mut ctx.accounts.profile;
+    require!(
+        profile.total_score >= realm.min_reputation_threshold,
+        ReputationError::InsufficientReputation
+    );
+    require!(
+        !profile.realm_memberships.contains(&realm.realm_id),
+        ReputationError::AlreadyRealmMember
+    );
+
+    let slot = profile
+        .realm_memberships
+        .iter_mut()
+        .find(|m| **m == Pubkey::default())
+        .ok_or(ReputationError::MembershipSlotsFull)?;
+    *slot = realm.realm_id;
+
+    // Rejoining keeps the reputation earned in the realm before leaving
+    ctx.accounts.realm_reputation.open(
+        realm.realm_id,
+        profile.wallet,
+        Clock::get()?.unix_timestamp,
+        ctx.bumps.realm_reputation,
+    );
+
+    realm.total_members = realm.total_members.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+
+    emit!(RealmMembershipChanged {
+        realm: realm.realm_id,
+        member: profile.wallet,
+        joined: true,
+        total_members: realm.total_members,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn leave_handler(ctx: Context<LeaveRealm>) -> Result<()> {
+    release_membership(&mut ctx.accounts.realm, &mut ctx.accounts.profile)
+}
+
+// remaining_accounts: co-signing realm admins, enough with `admin` to reach admin_threshold
+pub(crate) fn remove_handler(ctx: Context<RemoveMember>) -> Result<()> {
+    ctx.accounts.realm.check_admin_quorum(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+    release_membership(&mut ctx.accounts.realm, &mut ctx.accounts.member_profile)
+}
+
+fn release_membership(realm: &mut GovernanceRealm, profile: &mut ReputationProfile) -> Result<()> {
+    let slot = profile
+        .realm_memberships
+        .iter_mut()
+        .find(|m| **m == realm.realm_id)
+        .ok_or(ReputationError::NotRealmMember)?;
+    *slot = Pubkey::default();
+    realm.total_members = realm.total_members.saturating_sub(1);
+
+    emit!(RealmMembershipChanged {
+        realm: realm.realm_id,
+        member: profile.wallet,
+        joined: false,
+        total_members: realm.total_members,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RealmMembershipChanged {
+    pub realm: Pubkey,
+    pub member: Pubkey,
+    pub joined: bool,
+    pub total_members: u32,
+}
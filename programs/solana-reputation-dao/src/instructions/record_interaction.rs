@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{ReputationProfile, InteractionEvent};
+use crate::state::{ReputationProfile, InteractionEvent, InteractionPair, GovernanceRealm, RealmReputation};
 use crate::utils::{reputation_math, security};
+use crate::utils::constants::*;
 use crate::errors::ReputationError;
 
 #[derive(Accounts)]
@@ -23,19 +24,43 @@ pub struct RecordInteraction<'info> {
         init,
         payer = from_user,
         space = InteractionEvent::LEN,
+        seeds = [
+            b"interaction",
+            from_user.key().as_ref(),
+            to_user.key().as_ref(),
+            &from_profile.interaction_nonce.to_le_bytes(),
+        ],
+        bump
     )]
     pub interaction_event: Account<'info, InteractionEvent>,
 
+    #[account(
+        init_if_needed,
+        payer = from_user,
+        space = InteractionPair::LEN,
+        seeds = [b"pair", from_user.key().as_ref(), to_user.key().as_ref()],
+        bump
+    )]
+    pub interaction_pair: Account<'info, InteractionPair>,
+
     #[account(mut)]
     pub from_user: Signer<'info>,
 
     /// CHECK: validated by to_profile seeds vs to_user
     pub to_user: UncheckedAccount<'info>,
 
+    /// Realm both parties belong to; its algorithm weights scale only the
+    /// delta credited to the receiver's ledger there
+    pub realm: Option<Account<'info, GovernanceRealm>>,
+
+    /// Receiver's ledger in `realm`, required whenever a realm is given
+    #[account(mut)]
+    pub to_realm_reputation: Option<Account<'info, RealmReputation>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<RecordInteraction>,
     interaction_type: u8,
     weight: u16,
@@ -46,27 +71,30 @@ pub fn handler(
     require!(metadata.len() <= 256, ReputationError::MetadataTooLong);
 
     let clock = Clock::get()?;
-    let cooldown_period: i64 = match interaction_type {
-        0..=2 => 300,
-        3..=6 => 1800,
-        7..=9 => 7200,
-        _ => return err!(ReputationError::InvalidInteractionType),
-    };
 
     security::validate_interaction_limits(
         &ctx.accounts.from_profile,
         &ctx.accounts.to_profile,
+        &ctx.accounts.interaction_pair,
         interaction_type,
         clock.unix_timestamp,
-        cooldown_period,
     )?;
 
-    let mut reputation_delta = reputation_math::calculate_interaction_impact(
-        &ctx.accounts.from_profile,
-        &ctx.accounts.to_profile,
-        interaction_type,
-        weight,
-    )?;
+    if let Some(realm) = ctx.accounts.realm.as_ref() {
+        require!(
+            ctx.accounts.from_profile.realm_memberships.contains(&realm.realm_id)
+                && ctx.accounts.to_profile.realm_memberships.contains(&realm.realm_id),
+            ReputationError::NotRealmMember
+        );
+        let ledger = ctx
+            .accounts
+            .to_realm_reputation
+            .as_ref()
+            .ok_or(ReputationError::RealmReputationRequired)?;
+        ledger.ensure_for(&realm.realm_id, &ctx.accounts.to_user.key())?;
+    } else {
+        require!(ctx.accounts.to_realm_reputation.is_none(), ReputationError::RealmMismatch);
+    }
 
     let ai_score = ctx.accounts.from_profile.ai_validation_score;
     let ai_multiplier: u64 = if ai_score > 800 {
@@ -76,32 +104,42 @@ pub fn handler(
     } else {
         100
     };
-    reputation_delta = (reputation_delta * ai_multiplier) / 100;
-
     let trust_mult = ctx.accounts.from_profile.trust_multiplier.max(1);
-    reputation_delta = (reputation_delta * trust_mult) / 100;
+    let scale = |delta: u64| (delta.saturating_mul(ai_multiplier) / 100).saturating_mul(trust_mult) / 100;
+
+    // A realm's weights (each capped at 3x) shape the delta credited to both
+    // the global profile and that realm's ledger
+    let reputation_delta = scale(reputation_math::calculate_interaction_impact(
+        &ctx.accounts.from_profile,
+        &ctx.accounts.to_profile,
+        ctx.accounts.realm.as_deref(),
+        interaction_type,
+        weight,
+        clock.unix_timestamp,
+    )?);
+    let realm_delta = if ctx.accounts.realm.is_some() { reputation_delta } else { 0 };
 
+    let cat_index = reputation_math::interaction_category(interaction_type);
     let to_profile = &mut ctx.accounts.to_profile;
-    to_profile.total_score = to_profile.total_score.saturating_add(reputation_delta);
+    to_profile.credit(cat_index, reputation_delta, clock.unix_timestamp);
     to_profile.interaction_count = to_profile.interaction_count.saturating_add(1);
     to_profile.last_activity = clock.unix_timestamp;
 
-    let cat_index = match interaction_type {
-        3 | 4 => 0,           // Development
-        2 | 5 | 8 => 2,       // Community/Mentorship
-        1 => 2,               // Comment -> Community
-        6 => 4,               // Security
-        7 => 3,               // Innovation
-        9 => 1,               // Governance-ish
-        _ => 2,
-    };
-    if (cat_index as usize) < to_profile.category_scores.len() {
-        to_profile.category_scores[cat_index as usize] =
-            to_profile.category_scores[cat_index as usize].saturating_add(reputation_delta);
+    if let Some(realm_reputation) = ctx.accounts.to_realm_reputation.as_mut() {
+        realm_reputation.credit(cat_index, realm_delta, clock.unix_timestamp);
     }
 
     let from_profile = &mut ctx.accounts.from_profile;
     from_profile.last_activity = clock.unix_timestamp;
+    security::record_daily_interaction(from_profile, clock.unix_timestamp);
+    let nonce = from_profile.interaction_nonce;
+    from_profile.interaction_nonce = nonce.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+
+    let pair = &mut ctx.accounts.interaction_pair;
+    pair.from = ctx.accounts.from_user.key();
+    pair.to = ctx.accounts.to_user.key();
+    pair.bump = ctx.bumps.interaction_pair;
+    security::record_pair_interaction(pair, clock.unix_timestamp);
 
     let meta_hash = reputation_math::hash_metadata(&metadata);
     let event = &mut ctx.accounts.interaction_event;
@@ -112,13 +150,25 @@ pub fn handler(
     event.metadata_hash = meta_hash;
     event.reputation_delta = reputation_delta;
     event.timestamp = clock.unix_timestamp;
-    event.bump = 0;
+    event.nonce = nonce;
+    event.realm = ctx.accounts.realm.as_ref().map(|r| r.realm_id).unwrap_or_default();
+    event.realm_delta = realm_delta;
+    let retention = ctx
+        .accounts
+        .realm
+        .as_ref()
+        .map_or(DEFAULT_INTERACTION_RETENTION, |r| r.interaction_retention_seconds);
+    event.expires_at = clock.unix_timestamp.saturating_add(retention as i64);
+    event.open_disputes = 0;
+    event.reversed = false;
+    event.bump = ctx.bumps.interaction_event;
 
     emit!(InteractionRecorded {
         from: ctx.accounts.from_user.key(),
         to: ctx.accounts.to_user.key(),
         interaction_type,
         reputation_delta,
+        nonce,
         timestamp: clock.unix_timestamp,
     });
 
@@ -131,5 +181,6 @@ pub struct InteractionRecorded {
     pub to: Pubkey,
     pub interaction_type: u8,
     pub reputation_delta: u64,
+    pub nonce: u64,
     pub timestamp: i64,
 }
\ No newline at end of file
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    ReputationProfile, Badge, BadgeType, BadgeReceipt, BadgeDefinition, BadgeCollection, GovernanceRealm,
+    RealmReputation,
+};
+use crate::errors::ReputationError;
+use super::claim_badge::{check_eligible, verify_issuance};
+
+#[derive(Accounts)]
+pub struct RenewBadge<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(has_one = realm)]
+    pub badge_definition: Account<'info, BadgeDefinition>,
+    #[account(
+        mut,
+        seeds = [b"badge", user.key().as_ref(), badge_definition.key().as_ref()],
+        bump = badge_account.bump
+    )]
+    pub badge_account: Account<'info, BadgeReceipt>,
+    #[account(mut, seeds = [b"reputation", user.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    /// Page holding the badge when it is not stored inline
+    #[account(mut)]
+    pub badge_collection: Option<Box<Account<'info, BadgeCollection>>>,
+    /// Holder's ledger in `realm`, re-credited when the badge had lapsed
+    #[account(
+        mut,
+        seeds = [b"realm_rep", realm.key().as_ref(), user.key().as_ref()],
+        bump = realm_reputation.bump
+    )]
+    pub realm_reputation: Account<'info, RealmReputation>,
+    /// Issuer co-signature; omit when renewing with a merkle proof or by criteria
+    pub issuer: Option<Signer<'info>>,
+    pub user: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RenewBadge>, proof_hash: [u8; 32], merkle_proof: Vec<[u8; 32]>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let definition = &ctx.accounts.badge_definition;
+    require!(definition.validity_seconds > 0, ReputationError::BadgeDoesNotExpire);
+    // Renewal re-certifies, so the holder must still qualify as on a fresh claim
+    check_eligible(definition, &ctx.accounts.profile, ctx.accounts.badge_account.issuer_realm, now)?;
+    // Criteria badges re-certify on the profile's current numbers alone
+    if !definition.criteria.is_met(&ctx.accounts.profile) {
+        verify_issuance(
+            definition,
+            ctx.accounts.issuer.as_ref().map(|issuer| issuer.key()),
+            &ctx.accounts.user.key(),
+            &proof_hash,
+            &merkle_proof,
+        )?;
+    }
+
+    let expires_at = definition.badge_expiry(now);
+    let definition_id = definition.definition_id;
+    let issuer_realm = ctx.accounts.badge_account.issuer_realm;
+    let is_match = |b: &Badge| {
+        b.badge_type != BadgeType::None && b.issuer_realm == issuer_realm && b.definition_id == definition_id
+    };
+
+    let profile = &mut ctx.accounts.profile;
+    if let Some(slot) = profile.badges.iter_mut().find(|b| is_match(b)) {
+        slot.expires_at = expires_at;
+    } else {
+        let collection = ctx
+            .accounts
+            .badge_collection
+            .as_mut()
+            .ok_or(ReputationError::BadgeNotFound)?;
+        require_keys_eq!(collection.owner, profile.wallet, ReputationError::BadgeNotFound);
+        let slot = collection
+            .badges
+            .iter_mut()
+            .find(|b| is_match(b))
+            .ok_or(ReputationError::BadgeNotFound)?;
+        slot.expires_at = expires_at;
+    }
+
+    let score_bonus = ctx.accounts.badge_definition.score_bonus;
+    let receipt = &mut ctx.accounts.badge_account;
+    if receipt.lapsed {
+        // Other badges may have taken the room under the realm cap meanwhile
+        receipt.score_bonus = ctx.accounts.realm_reputation.credit_badge(receipt.category as usize, score_bonus, now);
+        receipt.lapsed = false;
+    }
+    receipt.expires_at = expires_at;
+    receipt.proof_hash = proof_hash;
+
+    emit!(BadgeRenewed {
+        owner: receipt.owner,
+        definition: receipt.definition,
+        expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeRenewed {
+    pub owner: Pubkey,
+    pub definition: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
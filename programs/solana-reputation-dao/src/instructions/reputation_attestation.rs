@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation, ReputationAttestation, BridgeRecord};
+use crate::utils::constants::*;
+use crate::errors::ReputationError;
+use super::bridge_reputation::ReputationBridged;
+
+#[derive(Accounts)]
+pub struct ExportReputationAttestation<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        seeds = [b"realm_rep", realm.key().as_ref(), realm_reputation.wallet.as_ref()],
+        bump = realm_reputation.bump
+    )]
+    pub realm_reputation: Account<'info, RealmReputation>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation: ReputationAttestation)]
+pub struct ImportReputationAttestation<'info> {
+    #[account(mut, seeds = [b"reputation", user.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = BridgeRecord::LEN,
+        seeds = [b"bridge", profile.key().as_ref(), attestation.source_realm.as_ref(), realm.key().as_ref()],
+        bump
+    )]
+    pub bridge_record: Account<'info, BridgeRecord>,
+    /// Destination realm; its attestation_authority must have signed the attestation
+    pub realm: Account<'info, GovernanceRealm>,
+    /// Importer's ledger in the destination realm, the only score credited
+    #[account(
+        mut,
+        seeds = [b"realm_rep", realm.key().as_ref(), user.key().as_ref()],
+        bump = realm_reputation.bump
+    )]
+    pub realm_reputation: Account<'info, RealmReputation>,
+    /// CHECK: instructions sysvar, read for the preceding Ed25519 verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Returns the attestation for the source realm's authority to sign off-chain
+pub(crate) fn export_handler(
+    ctx: Context<ExportReputationAttestation>,
+    validity_seconds: u32,
+) -> Result<ReputationAttestation> {
+    require!(
+        validity_seconds > 0 && validity_seconds <= MAX_ATTESTATION_VALIDITY,
+        ReputationError::InvalidAttestation
+    );
+    let clock = Clock::get()?;
+    let ledger = &ctx.accounts.realm_reputation;
+
+    Ok(ReputationAttestation {
+        source_program: crate::ID,
+        source_realm: ctx.accounts.realm.realm_id,
+        wallet: ledger.wallet,
+        total_score: ledger.total_score,
+        category_scores: ledger.category_scores,
+        slot: clock.slot,
+        expires_at: clock.unix_timestamp.saturating_add(validity_seconds as i64),
+    })
+}
+
+// Expects the Ed25519 sig-verify instruction immediately before this one
+pub(crate) fn import_handler(ctx: Context<ImportReputationAttestation>, attestation: ReputationAttestation) -> Result<()> {
+    let realm = &ctx.accounts.realm;
+    require!(realm.cross_realm_enabled, ReputationError::CrossRealmDisabled);
+    require!(realm.attestation_authority != Pubkey::default(), ReputationError::InvalidAttestation);
+    require_keys_eq!(attestation.wallet, ctx.accounts.user.key(), ReputationError::InvalidAttestation);
+    require!(
+        attestation.source_realm != realm.realm_id,
+        ReputationError::BridgeOperationFailed
+    );
+    require!(
+        ctx.accounts.profile.realm_memberships.contains(&realm.realm_id),
+        ReputationError::NotRealmMember
+    );
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= attestation.expires_at, ReputationError::AttestationExpired);
+
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current = load_current_index_checked(&instructions)?;
+    require!(current > 0, ReputationError::InvalidAttestation);
+    let verify_ix = load_instruction_at_checked(current as usize - 1, &instructions)?;
+    verify_ed25519_instruction(
+        &verify_ix,
+        &realm.attestation_authority,
+        &attestation.try_to_vec()?,
+    )?;
+
+    let factor = realm.reputation_algorithm.cross_realm_factor as u128;
+    let derived = ((attestation.total_score as u128).saturating_mul(factor) / 100).min(u64::MAX as u128) as u64;
+
+    // Each attestation is single-use: only strictly newer slots are accepted
+    let record = &mut ctx.accounts.bridge_record;
+    require!(
+        record.nonce == 0 || attestation.slot > record.last_attested_slot,
+        ReputationError::BridgeReplay
+    );
+    record.profile = ctx.accounts.profile.key();
+    record.source_realm = attestation.source_realm;
+    record.destination_realm = realm.realm_id;
+    record.last_attested_slot = attestation.slot;
+    record.bump = ctx.bumps.bridge_record;
+    let nonce = record.nonce;
+    let add = record.settle(nonce, derived, realm.max_bridged_reputation, clock.unix_timestamp)?;
+
+    // Imported reputation only counts inside the realm that vouched for it,
+    // plus the profile's cross_dao_reputation tally
+    ctx.accounts
+        .realm_reputation
+        .credit_in_proportion(&attestation.category_scores, add, clock.unix_timestamp)?;
+    let profile = &mut ctx.accounts.profile;
+    profile.cross_dao_reputation = profile.cross_dao_reputation.saturating_add(add);
+
+    emit!(ReputationBridged {
+        wallet: attestation.wallet,
+        source_program: attestation.source_program,
+        source_realm: attestation.source_realm,
+        realm: realm.realm_id,
+        amount: add,
+        total_bridged: record.total_bridged,
+        nonce,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Offsets header of the Ed25519 sig-verify precompile, one per signature
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+
+// Checks that `ix` is an Ed25519 precompile instruction carrying exactly one
+// signature by `signer` over `message`, with all data inline. The runtime has
+// already rejected the transaction if the signature itself is invalid.
+fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ReputationError::InvalidAttestation);
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_LEN && data[0] == 1,
+        ReputationError::InvalidAttestation
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = ED25519_OFFSETS_START;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+    // u16::MAX means "this instruction"; anything else could point the
+    // precompile at data we are not looking at
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        ReputationError::InvalidAttestation
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(ReputationError::InvalidAttestation)?;
+    let signed = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ReputationError::InvalidAttestation)?;
+    require!(pubkey == signer.as_ref(), ReputationError::InvalidAttestation);
+    require!(signed == message, ReputationError::InvalidAttestation);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ed25519_instruction_must_sign_expected_message_inline() {
+        // Same layout the precompile helper builds: header, pubkey, signature, message
+        let build = |signer: &Pubkey, message: &[u8], message_ix: u16| {
+            let pubkey_offset: u16 = 16;
+            let signature_offset: u16 = pubkey_offset + 32;
+            let message_offset: u16 = signature_offset + 64;
+            let mut data = vec![1u8, 0];
+            for v in [
+                signature_offset,
+                u16::MAX,
+                pubkey_offset,
+                u16::MAX,
+                message_offset,
+                message.len() as u16,
+                message_ix,
+            ] {
+                data.extend_from_slice(&v.to_le_bytes());
+            }
+            data.extend_from_slice(signer.as_ref());
+            data.extend_from_slice(&[0u8; 64]);
+            data.extend_from_slice(message);
+            Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+        };
+
+        let authority = Pubkey::new_unique();
+        let message = b"attestation";
+        assert!(verify_ed25519_instruction(&build(&authority, message, u16::MAX), &authority, message).is_ok());
+        assert!(verify_ed25519_instruction(&build(&authority, message, u16::MAX), &Pubkey::new_unique(), message).is_err());
+        assert!(verify_ed25519_instruction(&build(&authority, b"forged", u16::MAX), &authority, message).is_err());
+        assert!(verify_ed25519_instruction(&build(&authority, message, 0), &authority, message).is_err());
+
+        let mut wrong_program = build(&authority, message, u16::MAX);
+        wrong_program.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&wrong_program, &authority, message).is_err());
+    }
+}
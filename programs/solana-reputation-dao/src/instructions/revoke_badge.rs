@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    ReputationProfile, Badge, BadgeType, BadgeReceipt, BadgeCollection, BadgeDefinition, GovernanceRealm,
+    RealmReputation,
+};
+use crate::errors::ReputationError;
+use crate::utils::badge_token;
+use anchor_spl::token_2022::Token2022;
+
+#[derive(Accounts)]
+pub struct RevokeBadge<'info> {
+    pub realm: Account<'info, GovernanceRealm>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [
+            b"badge",
+            owner.key().as_ref(),
+            badge_definition.key().as_ref(),
+        ],
+        bump = badge_account.bump
+    )]
+    pub badge_account: Account<'info, BadgeReceipt>,
+    #[account(mut, has_one = realm, address = badge_account.definition)]
+    pub badge_definition: Account<'info, BadgeDefinition>,
+    #[account(mut, seeds = [b"reputation", owner.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, ReputationProfile>,
+    /// Page holding the badge when it is not stored inline
+    #[account(mut)]
+    pub badge_collection: Option<Box<Account<'info, BadgeCollection>>>,
+    /// Owner's ledger in `realm`, which holds the badge's bonus
+    #[account(
+        mut,
+        seeds = [b"realm_rep", realm.key().as_ref(), owner.key().as_ref()],
+        bump = realm_reputation.bump
+    )]
+    pub realm_reputation: Account<'info, RealmReputation>,
+    /// CHECK: the receipt's soulbound mint; required when the receipt has one
+    #[account(mut, address = badge_account.token_mint)]
+    pub badge_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: owner's associated token account for badge_mint, checked when burning
+    #[account(mut)]
+    pub holder_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: program PDA acting as permanent delegate
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<UncheckedAccount<'info>>,
+    pub token_program: Option<Program<'info, Token2022>>,
+    /// CHECK: receipt owner, receives the receipt's and mint's rent; bound by has_one and seeds
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+}
+
+// remaining_accounts: co-signing realm admins, enough with `admin` to reach admin_threshold
+pub(crate) fn handler(ctx: Context<RevokeBadge>) -> Result<()> {
+    let realm = &ctx.accounts.realm;
+    realm.check_admin_quorum(&ctx.accounts.admin.key(), ctx.remaining_accounts)?;
+
+    let receipt = &ctx.accounts.badge_account;
+    let bt = BadgeType::try_from(receipt.badge_type).map_err(|_| error!(ReputationError::InvalidBadgeProof))?;
+    let issuer_realm = receipt.issuer_realm;
+    let definition_id = ctx.accounts.badge_definition.definition_id;
+
+    let is_match = |b: &Badge| {
+        b.badge_type == bt && b.issuer_realm == issuer_realm && b.definition_id == definition_id
+    };
+    let profile = &mut ctx.accounts.profile;
+    if let Some(slot) = profile.badges.iter_mut().find(|b| is_match(b)) {
+        *slot = Badge::default();
+    } else {
+        let collection = ctx
+            .accounts
+            .badge_collection
+            .as_mut()
+            .ok_or(ReputationError::BadgeNotFound)?;
+        require_keys_eq!(collection.owner, receipt.owner, ReputationError::BadgeNotFound);
+        let slot = collection
+            .badges
+            .iter_mut()
+            .find(|b| is_match(b))
+            .ok_or(ReputationError::BadgeNotFound)?;
+        *slot = Badge::default();
+    }
+
+    let idx = receipt.category as usize;
+    // A lapsed badge's bonus was already removed by expire_badges
+    if !receipt.lapsed {
+        ctx.accounts.realm_reputation.debit_badge(idx, receipt.score_bonus);
+    }
+    profile.badge_count = profile.badge_count.saturating_sub(1);
+    profile.badge_category_counts[idx] = profile.badge_category_counts[idx].saturating_sub(1);
+
+    let definition = &mut ctx.accounts.badge_definition;
+    definition.supply = definition.supply.saturating_sub(1);
+
+    if receipt.token_mint != Pubkey::default() {
+        let (Some(mint), Some(holder_token_account), Some(authority), Some(token_program)) = (
+            ctx.accounts.badge_mint.as_ref(),
+            ctx.accounts.holder_token_account.as_ref(),
+            ctx.accounts.badge_authority.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        ) else {
+            return err!(ReputationError::InvalidBadgeToken);
+        };
+        badge_token::burn_soulbound_badge(
+            token_program.as_ref(),
+            &receipt.owner,
+            mint.as_ref(),
+            holder_token_account.as_ref(),
+            authority.as_ref(),
+            ctx.accounts.owner.as_ref(),
+            &[b"badge_authority", &[ctx.bumps.badge_authority]],
+        )?;
+    }
+
+    emit!(BadgeRevoked {
+        owner: receipt.owner,
+        badge_type: receipt.badge_type,
+        issuer_realm,
+        definition: receipt.definition,
+        revoked_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeRevoked {
+    pub owner: Pubkey,
+    pub badge_type: u8,
+    pub issuer_realm: Pubkey,
+    pub definition: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
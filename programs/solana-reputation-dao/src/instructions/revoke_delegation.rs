@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReputationProfile, DelegationRecord};
+use crate::errors::ReputationError;
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(
+        mut,
+        close = delegator,
+        has_one = delegator,
+        seeds = [b"delegation", delegator.key().as_ref(), delegation_record.delegatee.as_ref()],
+        bump = delegation_record.bump
+    )]
+    pub delegation_record: Account<'info, DelegationRecord>,
+    /// Delegator's profile, whose delegated_categories frees the record's categories
+    #[account(mut, seeds = [b"reputation", delegator.key().as_ref()], bump = delegator_profile.bump)]
+    pub delegator_profile: Account<'info, ReputationProfile>,
+    /// CHECK: delegator, receives the record's rent; bound by has_one and seeds
+    #[account(mut)]
+    pub delegator: UncheckedAccount<'info>,
+    /// The delegator, or anyone once the delegation has expired
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RevokeDelegation>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let record = &ctx.accounts.delegation_record;
+    require!(
+        ctx.accounts.authority.key() == record.delegator || record.is_expired(now),
+        ReputationError::DelegationNotExpired
+    );
+    let profile = &mut ctx.accounts.delegator_profile;
+    profile.delegated_categories &= !record.category_mask;
+
+    emit!(DelegationRevoked {
+        delegator: record.delegator,
+        delegatee: record.delegatee,
+        category_mask: record.category_mask,
+        revoked_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegatee: Pubkey,
+    pub category_mask: u8,
+    pub revoked_by: Pubkey,
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, AdminActionKind};
+
+#[derive(Accounts)]
+pub struct SetBridgePolicy<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetBridgePolicy>,
+    max_bridged_reputation: u64,
+    attestation_authority: Pubkey,
+) -> Result<()> {
+    ctx.accounts.realm.check_direct_admin(&ctx.accounts.admin.key())?;
+
+    ctx.accounts.realm.apply_admin_action(&AdminActionKind::SetBridgePolicy {
+        max_bridged_reputation,
+        attestation_authority,
+    })
+}
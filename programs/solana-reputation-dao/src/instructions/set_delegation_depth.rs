@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, AdminActionKind};
+
+#[derive(Accounts)]
+pub struct SetDelegationDepth<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetDelegationDepth>, max_delegation_depth: u8) -> Result<()> {
+    ctx.accounts.realm.check_direct_admin(&ctx.accounts.admin.key())?;
+
+    ctx.accounts
+        .realm
+        .apply_admin_action(&AdminActionKind::SetDelegationDepth { max_delegation_depth })
+}
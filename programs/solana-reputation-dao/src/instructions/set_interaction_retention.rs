@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, AdminActionKind};
+
+#[derive(Accounts)]
+pub struct SetInteractionRetention<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetInteractionRetention>, retention_seconds: u32) -> Result<()> {
+    ctx.accounts.realm.check_direct_admin(&ctx.accounts.admin.key())?;
+
+    ctx.accounts
+        .realm
+        .apply_admin_action(&AdminActionKind::SetInteractionRetention { retention_seconds })
+}
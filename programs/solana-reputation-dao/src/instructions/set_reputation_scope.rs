@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::{GovernanceRealm, AdminActionKind};
+
+#[derive(Accounts)]
+pub struct SetReputationScope<'info> {
+    #[account(mut)]
+    pub realm: Account<'info, GovernanceRealm>,
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetReputationScope>, local_reputation_only: bool) -> Result<()> {
+    ctx.accounts.realm.check_direct_admin(&ctx.accounts.admin.key())?;
+
+    ctx.accounts
+        .realm
+        .apply_admin_action(&AdminActionKind::SetReputationScope { local_reputation_only })
+}
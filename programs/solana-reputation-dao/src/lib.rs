@@ -1,22 +1,12 @@
 use anchor_lang::prelude::*;
-use crate::state::*;
+pub use crate::instructions::*;
+use crate::state::{AdminActionKind, BadgeDefinitionArgs, ReputationAttestation};
 
 pub mod errors;
 pub mod state;
 pub mod utils;
 pub mod instructions;
 
-use crate::instructions::{
-    bridge_reputation::BridgeReputation,
-    cast_reputation_vote::CastReputationVote,
-    claim_badge::ClaimBadge,
-    create_realm::CreateRealm,
-    delegate_reputation::DelegateReputation,
-    initialize_profile::InitializeProfile,
-    record_interaction::RecordInteraction,
-    update_algorithm::UpdateAlgorithm,
-};
-
 declare_id!("CiQgzVgLRjLC13vFjBnfsf6QGWnHgqGMZhrCnxZt95ha");
 
 #[program]
@@ -52,28 +42,26 @@ pub mod solana_reputation_dao {
         instructions::create_realm::handler(ctx, realm_name, algorithm_weights)
     }
 
-    pub fn delegate_reputation(
-        ctx: Context<DelegateReputation>,
+    pub fn delegate_reputation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DelegateReputation<'info>>,
         delegate_to: Pubkey,
         weight_percentage: u8,
+        category_mask: u8,
+        expires_at: i64,
     ) -> Result<()> {
-        instructions::delegate_reputation::handler(ctx, delegate_to, weight_percentage)
+        instructions::delegate_reputation::handler(ctx, delegate_to, weight_percentage, category_mask, expires_at)
     }
 
     pub fn claim_badge(
         ctx: Context<ClaimBadge>,
-        badge_type: u8,
         proof_hash: [u8; 32],
+        merkle_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::claim_badge::handler(ctx, badge_type, proof_hash)
+        instructions::claim_badge::handler(ctx, proof_hash, merkle_proof)
     }
 
-    pub fn bridge_reputation(
-        ctx: Context<BridgeReputation>,
-        source_realm: Pubkey,
-        bridge_weight: u8,
-    ) -> Result<()> {
-        instructions::bridge_reputation::handler(ctx, source_realm, bridge_weight)
+    pub fn bridge_reputation(ctx: Context<BridgeReputation>, bridge_weight: u8, nonce: u64) -> Result<()> {
+        instructions::bridge_reputation::handler(ctx, bridge_weight, nonce)
     }
 
     pub fn update_algorithm(
@@ -91,4 +79,146 @@ pub mod solana_reputation_dao {
             cross_realm_factor,
         )
     }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        title: String,
+        description: String,
+        category: u8,
+    ) -> Result<()> {
+        instructions::create_proposal::handler(ctx, title, description, category)
+    }
+
+    pub fn cast_vote<'info>(ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>, vote_type: u8) -> Result<()> {
+        instructions::cast_vote::handler(ctx, vote_type)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal::handler(ctx)
+    }
+
+    pub fn join_realm(ctx: Context<JoinRealm>) -> Result<()> {
+        instructions::realm_membership::join_handler(ctx)
+    }
+
+    pub fn leave_realm(ctx: Context<LeaveRealm>) -> Result<()> {
+        instructions::realm_membership::leave_handler(ctx)
+    }
+
+    pub fn remove_member(ctx: Context<RemoveMember>) -> Result<()> {
+        instructions::realm_membership::remove_handler(ctx)
+    }
+
+    pub fn apply_decay(ctx: Context<ApplyDecay>) -> Result<()> {
+        instructions::apply_decay::handler(ctx)
+    }
+
+    pub fn close_interaction_event(ctx: Context<CloseInteractionEvent>) -> Result<()> {
+        instructions::close_interaction_event::handler(ctx)
+    }
+
+    pub fn set_interaction_retention(
+        ctx: Context<SetInteractionRetention>,
+        retention_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_interaction_retention::handler(ctx, retention_seconds)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, reason: String) -> Result<()> {
+        instructions::dispute::open_handler(ctx, reason)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        uphold: bool,
+        trust_slash: u64,
+    ) -> Result<()> {
+        instructions::dispute::resolve_handler(ctx, uphold, trust_slash)
+    }
+
+    pub fn reverse_interaction(ctx: Context<ReverseInteraction>) -> Result<()> {
+        instructions::dispute::reverse_handler(ctx)
+    }
+
+    pub fn create_badge_definition(
+        ctx: Context<CreateBadgeDefinition>,
+        args: BadgeDefinitionArgs,
+    ) -> Result<()> {
+        instructions::create_badge_definition::handler(ctx, args)
+    }
+
+    pub fn revoke_badge(ctx: Context<RevokeBadge>) -> Result<()> {
+        instructions::revoke_badge::handler(ctx)
+    }
+
+    pub fn create_badge_collection(ctx: Context<CreateBadgeCollection>) -> Result<()> {
+        instructions::create_badge_collection::handler(ctx)
+    }
+
+    pub fn award_earned_badge(ctx: Context<AwardEarnedBadge>) -> Result<()> {
+        instructions::award_earned_badge::handler(ctx)
+    }
+
+    pub fn expire_badges<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireBadges<'info>>) -> Result<()> {
+        instructions::expire_badges::handler(ctx)
+    }
+
+    pub fn renew_badge(
+        ctx: Context<RenewBadge>,
+        proof_hash: [u8; 32],
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::renew_badge::handler(ctx, proof_hash, merkle_proof)
+    }
+
+    pub fn set_bridge_policy(
+        ctx: Context<SetBridgePolicy>,
+        max_bridged_reputation: u64,
+        attestation_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_bridge_policy::handler(ctx, max_bridged_reputation, attestation_authority)
+    }
+
+    pub fn set_reputation_scope(ctx: Context<SetReputationScope>, local_reputation_only: bool) -> Result<()> {
+        instructions::set_reputation_scope::handler(ctx, local_reputation_only)
+    }
+
+    pub fn export_reputation_attestation(
+        ctx: Context<ExportReputationAttestation>,
+        validity_seconds: u32,
+    ) -> Result<ReputationAttestation> {
+        instructions::reputation_attestation::export_handler(ctx, validity_seconds)
+    }
+
+    pub fn import_reputation_attestation(
+        ctx: Context<ImportReputationAttestation>,
+        attestation: ReputationAttestation,
+    ) -> Result<()> {
+        instructions::reputation_attestation::import_handler(ctx, attestation)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        instructions::revoke_delegation::handler(ctx)
+    }
+
+    pub fn set_delegation_depth(ctx: Context<SetDelegationDepth>, max_delegation_depth: u8) -> Result<()> {
+        instructions::set_delegation_depth::handler(ctx, max_delegation_depth)
+    }
+
+    pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, kind: AdminActionKind) -> Result<()> {
+        instructions::admin_actions::propose_handler(ctx, kind)
+    }
+
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        instructions::admin_actions::approve_handler(ctx)
+    }
+
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        instructions::admin_actions::execute_handler(ctx)
+    }
+
+    pub fn close_admin_action(ctx: Context<CloseAdminAction>) -> Result<()> {
+        instructions::admin_actions::close_handler(ctx)
+    }
+
 }
\ No newline at end of file
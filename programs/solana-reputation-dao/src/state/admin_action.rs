@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::state::governance_realm::GovernanceRealm;
+use crate::utils::constants::ADMIN_ACTION_TTL;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum AdminActionKind {
+    UpdateAlgorithm {
+        algorithm_weights: [u16; 5],
+        decay_factor: u8,
+        ai_enhancement: bool,
+        cross_realm_factor: u8,
+    },
+    AddAdmin { admin: Pubkey },
+    RemoveAdmin { admin: Pubkey },
+    RotateAdmin { old_admin: Pubkey, new_admin: Pubkey },
+    SetThreshold { admin_threshold: u8 },
+    SetBridgePolicy { max_bridged_reputation: u64, attestation_authority: Pubkey },
+    SetInteractionRetention { retention_seconds: u32 },
+    SetReputationScope { local_reputation_only: bool },
+    SetDelegationDepth { max_delegation_depth: u8 },
+}
+impl AdminActionKind {
+    pub const LEN: usize = 1 + 64; // tag + largest variant (RotateAdmin)
+}
+
+// Pending realm config change; executes once admin_threshold admins approved,
+// within ADMIN_ACTION_TTL of being proposed and before the admin set changes
+#[account]
+pub struct AdminAction {
+    pub realm: Pubkey,              // 32
+    pub action_id: u64,             // 8
+    pub proposer: Pubkey,           // 32
+    pub kind: AdminActionKind,      // 65
+    pub approvals: [Pubkey; 3],     // 96 (approving admins, default = empty)
+    pub created_at: i64,            // 8
+    pub executed: bool,             // 1
+    pub bump: u8,                   // 1
+    pub admin_epoch: u32,           // 4 (realm admin_epoch when proposed)
+}
+impl AdminAction {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + AdminActionKind::LEN + 96 + 8 + 1 + 1 + 4;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.created_at.saturating_add(ADMIN_ACTION_TTL)
+    }
+
+    // Approvals were given by the admin set of the time; once it changes,
+    // even an admin rotated out and back in has to approve again
+    pub fn is_stale(&self, realm: &GovernanceRealm) -> bool {
+        self.admin_epoch != realm.admin_epoch
+    }
+
+    pub fn approval_count(&self, realm: &GovernanceRealm) -> u8 {
+        if self.is_stale(realm) {
+            return 0;
+        }
+        self.approvals
+            .iter()
+            .filter(|a| **a != Pubkey::default() && realm.is_admin(a))
+            .count() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approvals_lapse_with_admin_changes_and_expiry() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut realm = GovernanceRealm {
+            admin_wallets: [a, b, Pubkey::default()],
+            admin_threshold: 2,
+            ..Default::default()
+        };
+        let action = AdminAction {
+            realm: Pubkey::default(),
+            action_id: 0,
+            proposer: a,
+            kind: AdminActionKind::SetThreshold { admin_threshold: 1 },
+            approvals: [a, b, Pubkey::default()],
+            created_at: 0,
+            executed: false,
+            bump: 0,
+            admin_epoch: 0,
+        };
+        assert_eq!(action.approval_count(&realm), 2);
+
+        // Rotating b out and back in leaves the same admins, but b's approval
+        // was given before and no longer counts, nor does a's
+        realm.apply_admin_action(&AdminActionKind::RotateAdmin { old_admin: b, new_admin: c }).unwrap();
+        realm.apply_admin_action(&AdminActionKind::RotateAdmin { old_admin: c, new_admin: b }).unwrap();
+        assert!(action.is_stale(&realm));
+        assert_eq!(action.approval_count(&realm), 0);
+
+        // Actions lapse ADMIN_ACTION_TTL after they were proposed
+        assert!(!action.is_expired(ADMIN_ACTION_TTL - 1));
+        assert!(action.is_expired(ADMIN_ACTION_TTL));
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// Canonical cross-program reputation attestation. The borsh encoding of this
+// struct is exactly the message the source realm's authority signs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ReputationAttestation {
+    pub source_program: Pubkey,
+    pub source_realm: Pubkey,
+    pub wallet: Pubkey,
+    pub total_score: u64,
+    pub category_scores: [u64; 5],
+    pub slot: u64,
+    pub expires_at: i64,
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::utils::constants::*;
+use crate::state::ReputationProfile;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BadgeType {
+    #[default]
     None,
     Developer,
     GovernanceParticipant,
@@ -14,12 +17,9 @@ pub enum BadgeType {
     AIValidator,
     Custom,
 }
-impl Default for BadgeType {
-    fn default() -> Self { BadgeType::None }
-}
 impl TryFrom<u8> for BadgeType {
     type Error = ();
-    fn try_from(v: u8) -> Result<Self, Self::Error> {
+    fn try_from(v: u8) -> std::result::Result<Self, Self::Error> {
         Ok(match v {
             0 => BadgeType::None,
             1 => BadgeType::Developer,
@@ -37,22 +37,160 @@ impl TryFrom<u8> for BadgeType {
     }
 }
 
+impl BadgeType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BadgeType::None => "None",
+            BadgeType::Developer => "Developer",
+            BadgeType::GovernanceParticipant => "Governance Participant",
+            BadgeType::CommunityBuilder => "Community Builder",
+            BadgeType::SecurityAuditor => "Security Auditor",
+            BadgeType::Innovation => "Innovation",
+            BadgeType::Mentor => "Mentor",
+            BadgeType::EarlyAdopter => "Early Adopter",
+            BadgeType::CrossChainBridge => "Cross-Chain Bridge",
+            BadgeType::AIValidator => "AI Validator",
+            BadgeType::Custom => "Custom",
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
 pub struct Badge {
     pub badge_type: BadgeType,      // 1 byte (borsh enum)
+    pub definition_id: u16,         // 2 bytes
     pub earned_at: i64,             // 8 bytes
     pub issuer_realm: Pubkey,       // 32 bytes
     pub metadata_hash: [u8; 32],    // 32 bytes
+    pub expires_at: i64,            // 8 bytes (0 = never lapses)
 }
 
 #[account]
 pub struct BadgeReceipt {
     pub owner: Pubkey,              // 32
     pub badge_type: u8,             // 1
+    pub issuer_realm: Pubkey,       // 32
+    pub definition: Pubkey,         // 32
+    pub score_bonus: u64,           // 8 (as credited to the ledger, after the realm badge cap)
+    pub category: u8,               // 1
     pub proof_hash: [u8; 32],       // 32
     pub earned_at: i64,             // 8
+    pub expires_at: i64,            // 8 (0 = never lapses)
+    pub lapsed: bool,               // 1 (bonus removed by expire_badges until renewed)
+    pub token_mint: Pubkey,         // 32 (soulbound Token-2022 mint, Pubkey::default() if none)
     pub bump: u8,                   // 1
 }
 impl BadgeReceipt {
-    pub const LEN: usize = 8 + 32 + 1 + 32 + 8 + 1;
-}
\ No newline at end of file
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 32 + 8 + 1 + 32 + 8 + 8 + 1 + 32 + 1;
+}
+
+// Overflow page for badges beyond the profile's inline MAX_BADGES
+#[account]
+pub struct BadgeCollection {
+    pub owner: Pubkey,                            // 32
+    pub page: u32,                                // 4
+    pub badges: [Badge; BADGES_PER_COLLECTION],   // 16 * 83 = 1328
+    pub bump: u8,                                 // 1
+}
+impl BadgeCollection {
+    pub const LEN: usize = 8 + 32 + 4 + 83 * BADGES_PER_COLLECTION + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BadgeEligibility {
+    pub min_total_score: u64,       // 8
+    pub min_category_score: u64,    // 8 (in the definition's category)
+    pub members_only: bool,         // 1
+}
+
+// On-chain thresholds for badges anyone can award once the profile qualifies
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BadgeCriteria {
+    pub auto_award: bool,                   // 1
+    pub min_category_scores: [u64; 5],      // 40
+    pub min_interactions: u32,              // 4
+    pub min_votes_cast: u32,                // 4
+    pub min_cross_dao_reputation: u64,      // 8
+}
+impl BadgeCriteria {
+    pub fn is_met(&self, profile: &ReputationProfile) -> bool {
+        self.auto_award
+            && profile
+                .category_scores
+                .iter()
+                .zip(self.min_category_scores.iter())
+                .all(|(score, min)| score >= min)
+            && profile.interaction_count >= self.min_interactions
+            && profile.votes_cast >= self.min_votes_cast
+            && profile.cross_dao_reputation >= self.min_cross_dao_reputation
+    }
+
+    // Anyone can trigger an award, so the bar must be reputation the profile
+    // earned rather than counters a fresh profile already satisfies
+    pub fn requires_earned_reputation(&self) -> bool {
+        self.min_category_scores
+            .iter()
+            .fold(0u64, |sum, min| sum.saturating_add(*min))
+            >= MIN_AUTO_AWARD_SCORE
+    }
+}
+
+#[account]
+pub struct BadgeDefinition {
+    pub realm: Pubkey,              // 32
+    pub definition_id: u16,         // 2
+    pub badge_type: u8,             // 1
+    pub name: [u8; 32],             // 32
+    pub uri: [u8; 128],             // 128
+    pub score_bonus: u64,           // 8
+    pub category: u8,               // 1
+    pub max_supply: u32,            // 4 (0 = unlimited)
+    pub supply: u32,                // 4
+    pub expires_at: i64,            // 8 (0 = never; no claims after this)
+    pub validity_seconds: i64,      // 8 (0 = awarded badges never lapse)
+    pub eligibility: BadgeEligibility, // 17
+    pub criteria: BadgeCriteria,    // 57
+    pub issuer: Pubkey,             // 32 (Pubkey::default() when only merkle proofs are accepted)
+    pub merkle_root: [u8; 32],      // 32 (all zero when only the issuer can award)
+    pub created_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+impl BadgeDefinition {
+    pub const LEN: usize = 8 + 32 + 2 + 1 + 32 + 128 + 8 + 1 + 4 + 4 + 8 + 8 + 17 + 57 + 32 + 32 + 8 + 1;
+
+    pub fn name(&self) -> String {
+        let end = self.name.iter().position(|&b| b == 0).unwrap_or(self.name.len());
+        String::from_utf8_lossy(&self.name[..end]).into_owned()
+    }
+
+    // Expiry stamped on a badge awarded or renewed at `now`
+    pub fn badge_expiry(&self, now: i64) -> i64 {
+        if self.validity_seconds > 0 {
+            now.saturating_add(self.validity_seconds)
+        } else {
+            0
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        let end = self.uri.iter().position(|&b| b == 0).unwrap_or(self.uri.len());
+        String::from_utf8_lossy(&self.uri[..end]).into_owned()
+    }
+}
+
+// Creation parameters for a realm badge definition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BadgeDefinitionArgs {
+    pub badge_type: u8,
+    pub name: String,
+    pub uri: String,
+    pub score_bonus: u64,
+    pub category: u8,
+    pub max_supply: u32,
+    pub expires_at: i64,
+    pub validity_seconds: i64,
+    pub eligibility: BadgeEligibility,
+    pub criteria: BadgeCriteria,
+    pub issuer: Pubkey,
+    pub merkle_root: [u8; 32],
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::errors::ReputationError;
+use crate::utils::constants::BRIDGE_COOLDOWN_SECONDS;
+
+// Reputation already bridged into a profile from one source realm into one
+// destination realm
+#[account]
+#[derive(Default)]
+pub struct BridgeRecord {
+    pub profile: Pubkey,            // 32
+    pub source_realm: Pubkey,       // 32
+    pub destination_realm: Pubkey,  // 32
+    pub total_bridged: u64,         // 8
+    pub nonce: u64,                 // 8 (completed bridges; the next call must pass this value)
+    pub last_bridged_at: i64,       // 8
+    pub last_attested_slot: u64,    // 8 (newest attestation slot imported)
+    pub bump: u8,                   // 1
+}
+impl BridgeRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    // Brings the record's cumulative total up to min(derived, cap) and returns the
+    // increment. `nonce` must match the record's completed-bridge count, and
+    // re-bridging an unchanged source score adds nothing, so neither a replayed
+    // call nor a repeated one can stack the same reputation.
+    pub fn settle(&mut self, nonce: u64, derived: u64, cap: u64, now: i64) -> Result<u64> {
+        require!(self.nonce == nonce, ReputationError::BridgeReplay);
+        require!(
+            self.nonce == 0 || now.saturating_sub(self.last_bridged_at) >= BRIDGE_COOLDOWN_SECONDS,
+            ReputationError::BridgeCooldownActive
+        );
+        let add = derived.min(cap).saturating_sub(self.total_bridged);
+        require!(add > 0, ReputationError::BridgeCapReached);
+
+        self.total_bridged = self.total_bridged.saturating_add(add);
+        self.nonce = self.nonce.checked_add(1).ok_or(ReputationError::MathOverflow)?;
+        self.last_bridged_at = now;
+        Ok(add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn bridge_settles_to_capped_total_with_cooldown() {
+        let mut record = BridgeRecord::default();
+        assert_eq!(record.settle(0, 300, 500, NOW).unwrap(), 300);
+
+        // Same source score again: cooldown first, then nothing left to add
+        assert_eq!(
+            record.settle(1, 300, 500, NOW + 60).unwrap_err(),
+            error!(ReputationError::BridgeCooldownActive)
+        );
+        let later = NOW + BRIDGE_COOLDOWN_SECONDS;
+        assert_eq!(
+            record.settle(1, 300, 500, later).unwrap_err(),
+            error!(ReputationError::BridgeCapReached)
+        );
+
+        // Source score grew past the cap: only the remainder up to the cap
+        assert_eq!(record.settle(1, 900, 500, later).unwrap(), 200);
+        assert_eq!(record.total_bridged, 500);
+        assert_eq!(record.nonce, 2);
+        assert_eq!(
+            record.settle(2, 900, 500, later + BRIDGE_COOLDOWN_SECONDS).unwrap_err(),
+            error!(ReputationError::BridgeCapReached)
+        );
+    }
+
+    #[test]
+    fn bridge_rejects_stale_and_future_nonces() {
+        let mut record = BridgeRecord::default();
+        record.settle(0, 100, 500, NOW).unwrap();
+        let later = NOW + BRIDGE_COOLDOWN_SECONDS;
+        for nonce in [0, 2] {
+            assert_eq!(
+                record.settle(nonce, 200, 500, later).unwrap_err(),
+                error!(ReputationError::BridgeReplay)
+            );
+        }
+        // A rejected call leaves the record untouched
+        assert_eq!((record.total_bridged, record.nonce), (100, 1));
+        assert_eq!(record.settle(1, 200, 500, later).unwrap(), 100);
+    }
+}
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::state::ReputationProfile;
+use crate::errors::ReputationError;
+use crate::utils::constants::{DELEGATION_GENERAL_BIT, MAX_DELEGATION_DEPTH, PROPOSAL_CATEGORY_GENERAL};
+
+// One delegator -> delegatee delegation, keyed by the pair, covering every
+// category in its mask. The delegator's profile tracks which categories its
+// records hold (delegated_categories), so each category goes to at most one
+// delegatee while topics can go to different ones. Only the percentage is
+// stored: the power is worked out when a vote carries it.
+#[account]
+pub struct DelegationRecord {
+    pub delegator: Pubkey,          // 32
+    pub delegatee: Pubkey,          // 32
+    pub category_mask: u8,          // 1 (bit per CAT_* index, plus DELEGATION_GENERAL_BIT)
+    pub weight_percentage: u8,      // 1
+    pub created_at: i64,            // 8
+    pub expires_at: i64,            // 8 (0 = never)
+    pub bump: u8,                   // 1
+}
+impl DelegationRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 1;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn covers(&self, category: u8) -> bool {
+        self.category_mask & category_bit(category) != 0
+    }
+
+    // Power carried on a proposal in `category` created at `created_at`: the
+    // percentage of exactly the weight the delegator would vote with directly,
+    // i.e. their score in that category (or the total for general proposals)
+    // as it stood then, lowered by any decay or slash since
+    pub fn voting_power(&self, delegator: &ReputationProfile, category: u8, created_at: i64) -> u64 {
+        let score = delegator.score_history.weight_at(
+            created_at,
+            delegator.total_score,
+            &delegator.category_scores,
+            category,
+        );
+        (score as u128 * self.weight_percentage as u128 / 100) as u64
+    }
+}
+
+// Bit of a CAT_* index, or of general proposals, in delegated_categories
+pub fn category_bit(category: u8) -> u8 {
+    if category == PROPOSAL_CATEGORY_GENERAL {
+        DELEGATION_GENERAL_BIT
+    } else {
+        1 << category
+    }
+}
+
+// Vote-time status of a delegator passed as part of a delegation chain
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProxyState {
+    NotVoted,
+    Direct,
+    Proxied(Pubkey), // carried by an earlier vote from this delegate
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DelegationEdge {
+    pub delegator: Pubkey,
+    pub delegatee: Pubkey,
+    pub power: u64,
+    pub state: ProxyState,
+}
+
+// Sums the power `voter` carries for the given edges. Each edge must hang off
+// the voter or off an earlier edge's delegator, so every delegator sits on a
+// path to the voter no longer than `max_depth`. Delegators who voted directly
+// end their chain; power proxied by someone further down is reclaimed, since
+// the voter is closer on the delegator's only path.
+pub fn resolve_delegation_chain(voter: &Pubkey, edges: &[DelegationEdge], max_depth: u8) -> Result<u64> {
+    let mut depths: Vec<(Pubkey, u8)> = Vec::with_capacity(edges.len());
+    let mut total: u64 = 0;
+    for edge in edges {
+        require!(
+            edge.delegator != *voter && !depths.iter().any(|(d, _)| *d == edge.delegator),
+            ReputationError::InvalidDelegationChain
+        );
+        require!(
+            edge.state != ProxyState::Direct && edge.state != ProxyState::Proxied(*voter),
+            ReputationError::InvalidDelegationChain
+        );
+        let parent_depth = if edge.delegatee == *voter {
+            0
+        } else {
+            depths
+                .iter()
+                .find(|(d, _)| *d == edge.delegatee)
+                .map(|(_, depth)| *depth)
+                .ok_or(ReputationError::InvalidDelegationChain)?
+        };
+        let depth = parent_depth + 1;
+        require!(depth <= max_depth, ReputationError::DelegationDepthExceeded);
+        depths.push((edge.delegator, depth));
+        total = total.saturating_add(edge.power);
+    }
+    Ok(total)
+}
+
+// `path` is the delegatee's onward chain of delegatees; the new edge from
+// `delegator` must not close a loop or make that chain longer than allowed.
+// Only the downstream side is known here: nothing indexes who delegates to
+// `delegator`, so a chain may still grow past the limit from upstream. Depth
+// is enforced where it matters, when cast_vote resolves the chain against the
+// realm's max_delegation_depth and rejects any delegator further up, so their
+// power is never carried.
+pub fn check_delegation_path(delegator: &Pubkey, path: &[Pubkey]) -> Result<()> {
+    require!(!path.contains(delegator), ReputationError::DelegationCycle);
+    require!(path.len() < MAX_DELEGATION_DEPTH as usize, ReputationError::DelegationDepthExceeded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn voting_power_follows_the_delegators_bucket_at_creation() {
+        let mut profile = ReputationProfile {
+            total_score: 1_000,
+            category_scores: [100, 200, 300, 0, 400],
+            ..Default::default()
+        };
+        // One record covers Security, Innovation and general proposals
+        let record = DelegationRecord {
+            delegator: Pubkey::new_unique(),
+            delegatee: Pubkey::new_unique(),
+            category_mask: category_bit(4) | category_bit(3) | DELEGATION_GENERAL_BIT,
+            weight_percentage: 50,
+            created_at: NOW - 20,
+            expires_at: 0,
+            bump: 0,
+        };
+        assert!(record.covers(4) && record.covers(PROPOSAL_CATEGORY_GENERAL) && !record.covers(2));
+        assert_eq!(record.voting_power(&profile, 4, NOW), 200);
+        assert_eq!(record.voting_power(&profile, 3, NOW), 0);
+        assert_eq!(record.voting_power(&profile, PROPOSAL_CATEGORY_GENERAL, NOW), 500);
+
+        // Reputation earned after the proposal carries nothing extra
+        profile.credit(4, 600, NOW + 1);
+        assert_eq!(record.voting_power(&profile, 4, NOW), 200);
+        assert_eq!(record.voting_power(&profile, 4, NOW + 2), 500);
+
+        // A slash lowers the carried power straight away
+        profile.category_scores[4] = 100;
+        assert_eq!(record.voting_power(&profile, 4, NOW), 50);
+    }
+
+    #[test]
+    fn delegation_chain_resolves_within_depth() {
+        let (a, b, c, d) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let edge = |delegator, delegatee, power, state| DelegationEdge { delegator, delegatee, power, state };
+
+        // A -> B -> C: C carries both; A's power proxied earlier by D is reclaimed
+        let chain = [edge(b, c, 40, ProxyState::NotVoted), edge(a, b, 25, ProxyState::Proxied(d))];
+        assert_eq!(resolve_delegation_chain(&c, &chain, 2).unwrap(), 65);
+        assert!(resolve_delegation_chain(&c, &chain, 1).is_err());
+
+        // Order matters: A's delegatee must already be on the chain
+        let reversed = [chain[1], chain[0]];
+        assert!(resolve_delegation_chain(&c, &reversed, 2).is_err());
+
+        // Direct voters end their chain; nothing is counted twice
+        assert!(resolve_delegation_chain(&c, &[edge(b, c, 40, ProxyState::Direct)], 2).is_err());
+        assert!(resolve_delegation_chain(&c, &[edge(b, c, 40, ProxyState::Proxied(c))], 2).is_err());
+        assert!(resolve_delegation_chain(&c, &[chain[0], chain[0]], 2).is_err());
+        assert!(resolve_delegation_chain(&c, &[edge(a, d, 25, ProxyState::NotVoted)], 2).is_err());
+    }
+
+    #[test]
+    fn delegation_path_rejects_cycles_and_long_chains() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(check_delegation_path(&a, &[]).is_ok());
+        assert!(check_delegation_path(&a, &[b, c]).is_ok());
+        assert!(check_delegation_path(&a, &[b, a]).is_err());
+
+        let long: Vec<Pubkey> = (0..MAX_DELEGATION_DEPTH).map(|_| Pubkey::new_unique()).collect();
+        assert!(check_delegation_path(&a, &long).is_err());
+        assert!(check_delegation_path(&a, &long[1..]).is_ok());
+    }
+}
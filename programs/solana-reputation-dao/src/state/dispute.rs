@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisputeStatus {
+    #[default]
+    Open,
+    Upheld,
+    Dismissed,
+    Reversed,
+}
+
+#[account]
+pub struct Dispute {
+    pub interaction_event: Pubkey,  // 32
+    pub realm: Pubkey,              // 32 (realm whose admins arbitrate; part of the seeds)
+    pub opened_by: Pubkey,          // 32
+    pub reason_hash: [u8; 32],      // 32
+    pub status: DisputeStatus,      // 1
+    pub trust_slash: u64,           // 8 (taken off the sender's trust_multiplier on reversal)
+    pub opened_at: i64,             // 8
+    pub resolved_at: i64,           // 8
+    pub bump: u8,                   // 1
+}
+impl Dispute {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 1;
+}
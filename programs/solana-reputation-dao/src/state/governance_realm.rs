@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::state::admin_action::AdminActionKind;
+use crate::errors::ReputationError;
+use crate::utils::constants::*;
 
 #[account]
 #[derive(Default)]
@@ -16,10 +19,140 @@ pub struct GovernanceRealm {
     pub cross_realm_enabled: bool,              // 1
     pub ai_moderation_enabled: bool,            // 1
     pub created_at: i64,                        // 8
+    pub proposal_count: u64,                    // 8
+    pub interaction_retention_seconds: u32,     // 4
+    pub badge_definition_count: u16,            // 2
+    pub max_bridged_reputation: u64,            // 8 (cap on reputation bridged in from another realm)
+    pub local_reputation_only: bool,            // 1 (gate on RealmReputation instead of the global profile)
+    pub attestation_authority: Pubkey,          // 32 (signer trusted for imported attestations, default = none)
+    pub max_delegation_depth: u8,               // 1 (delegation hops resolved when voting)
+    pub admin_threshold: u8,                    // 1 (admin approvals needed for an AdminAction)
+    pub admin_action_count: u64,                // 8 (next AdminAction seed)
+    pub admin_epoch: u32,                       // 4 (bumped whenever the admin set changes)
     pub bump: u8,                                // 1
 }
 impl GovernanceRealm {
-    pub const LEN: usize = 8 + 32 + 32 + 96 + 13 + 4 + 2 + 8 + 33 + 8 + 4 + 1 + 1 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 96 + 13 + 4 + 2 + 8 + 33 + 8 + 4 + 1 + 1 + 8 + 8 + 4 + 2 + 8 + 1 + 32 + 1 + 1 + 1 + 8 + 4;
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.admin_wallets.contains(key)
+    }
+
+    // Realm config setters that act on one signature; above threshold 1 the
+    // same changes go through an AdminAction instead
+    pub fn check_direct_admin(&self, admin: &Pubkey) -> Result<()> {
+        require!(self.is_admin(admin), ReputationError::AdminRequired);
+        require!(self.admin_threshold <= 1, ReputationError::MultisigRequired);
+        Ok(())
+    }
+
+    // Admin instructions with no AdminAction route (badge definitions,
+    // revocations, removals, dispute rulings) take the threshold's signatures
+    // directly: the admin plus co-signing admins passed as remaining accounts
+    pub fn check_admin_quorum(&self, admin: &Pubkey, co_signers: &[AccountInfo]) -> Result<()> {
+        require!(self.is_admin(admin), ReputationError::AdminRequired);
+        let mut signed = vec![*admin];
+        for info in co_signers {
+            require!(info.is_signer && self.is_admin(info.key), ReputationError::AdminRequired);
+            if !signed.contains(info.key) {
+                signed.push(*info.key);
+            }
+        }
+        require!(signed.len() >= self.admin_threshold.max(1) as usize, ReputationError::AdminQuorumRequired);
+        Ok(())
+    }
+
+    pub fn admin_count(&self) -> u8 {
+        self.admin_wallets.iter().filter(|a| **a != Pubkey::default()).count() as u8
+    }
+
+    // Applies an approved (or, below threshold 2, direct) admin change. The
+    // admin set must still be able to reach the threshold afterwards, and any
+    // change to it moves admin_epoch so pending approvals stop counting.
+    pub fn apply_admin_action(&mut self, kind: &AdminActionKind) -> Result<()> {
+        match kind {
+            AdminActionKind::UpdateAlgorithm {
+                algorithm_weights,
+                decay_factor,
+                ai_enhancement,
+                cross_realm_factor,
+            } => {
+                ReputationAlgorithm::validate_weights(algorithm_weights)?;
+                require!(
+                    *decay_factor <= MAX_DECAY_RATE && *cross_realm_factor <= MAX_CROSS_REALM_FACTOR,
+                    ReputationError::InvalidAlgorithmParameters
+                );
+                let algo = &mut self.reputation_algorithm;
+                algo.development_weight = algorithm_weights[0];
+                algo.governance_weight = algorithm_weights[1];
+                algo.community_weight = algorithm_weights[2];
+                algo.innovation_weight = algorithm_weights[3];
+                algo.security_weight = algorithm_weights[4];
+                algo.decay_factor = *decay_factor;
+                algo.ai_enhancement = *ai_enhancement;
+                algo.cross_realm_factor = *cross_realm_factor;
+            }
+            AdminActionKind::AddAdmin { admin } => {
+                require!(
+                    *admin != Pubkey::default() && !self.is_admin(admin),
+                    ReputationError::InvalidAdminChange
+                );
+                let slot = self
+                    .admin_wallets
+                    .iter_mut()
+                    .find(|a| **a == Pubkey::default())
+                    .ok_or(ReputationError::AdminSlotsFull)?;
+                *slot = *admin;
+            }
+            AdminActionKind::RemoveAdmin { admin } => {
+                require!(self.is_admin(admin), ReputationError::InvalidAdminChange);
+                require!(self.admin_count() > self.admin_threshold, ReputationError::InvalidAdminThreshold);
+                for slot in self.admin_wallets.iter_mut().filter(|a| **a == *admin) {
+                    *slot = Pubkey::default();
+                }
+            }
+            AdminActionKind::RotateAdmin { old_admin, new_admin } => {
+                require!(
+                    self.is_admin(old_admin) && *new_admin != Pubkey::default() && !self.is_admin(new_admin),
+                    ReputationError::InvalidAdminChange
+                );
+                for slot in self.admin_wallets.iter_mut().filter(|a| **a == *old_admin) {
+                    *slot = *new_admin;
+                }
+            }
+            AdminActionKind::SetThreshold { admin_threshold } => {
+                require!(
+                    *admin_threshold >= 1 && *admin_threshold <= self.admin_count(),
+                    ReputationError::InvalidAdminThreshold
+                );
+                self.admin_threshold = *admin_threshold;
+            }
+            AdminActionKind::SetBridgePolicy { max_bridged_reputation, attestation_authority } => {
+                self.max_bridged_reputation = *max_bridged_reputation;
+                self.attestation_authority = *attestation_authority;
+            }
+            AdminActionKind::SetInteractionRetention { retention_seconds } => {
+                // Events must stay disputable for the whole dispute window
+                require!(*retention_seconds >= MIN_INTERACTION_RETENTION, ReputationError::RetentionTooShort);
+                self.interaction_retention_seconds = *retention_seconds;
+            }
+            AdminActionKind::SetReputationScope { local_reputation_only } => {
+                self.local_reputation_only = *local_reputation_only;
+            }
+            AdminActionKind::SetDelegationDepth { max_delegation_depth } => {
+                // 0 turns off proxy voting; delegates then vote with their own score
+                require!(*max_delegation_depth <= MAX_DELEGATION_DEPTH, ReputationError::DelegationDepthExceeded);
+                self.max_delegation_depth = *max_delegation_depth;
+            }
+        }
+        if matches!(
+            kind,
+            AdminActionKind::AddAdmin { .. } | AdminActionKind::RemoveAdmin { .. } | AdminActionKind::RotateAdmin { .. }
+        ) {
+            self.admin_epoch = self.admin_epoch.wrapping_add(1);
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -32,4 +165,109 @@ pub struct ReputationAlgorithm {
     pub decay_factor: u8,
     pub ai_enhancement: bool,
     pub cross_realm_factor: u8,
-}
\ No newline at end of file
+}
+impl ReputationAlgorithm {
+    // At least one category must count, and none may exceed MAX_CATEGORY_WEIGHT
+    pub fn validate_weights(weights: &[u16; 5]) -> Result<()> {
+        require!(
+            weights.iter().any(|&w| w > 0) && weights.iter().all(|&w| w <= MAX_CATEGORY_WEIGHT),
+            ReputationError::InvalidAlgorithmWeights
+        );
+        Ok(())
+    }
+
+    // Weight for a category index (Dev, Gov, Community, Innovation, Security), 100 = 1.00x
+    pub fn category_weight(&self, category: usize) -> u16 {
+        match category {
+            0 => self.development_weight,
+            1 => self.governance_weight,
+            2 => self.community_weight,
+            3 => self.innovation_weight,
+            4 => self.security_weight,
+            _ => 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admin_changes_keep_threshold_reachable() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut realm = GovernanceRealm {
+            admin_wallets: [a, Pubkey::default(), Pubkey::default()],
+            admin_threshold: 1,
+            ..Default::default()
+        };
+
+        realm.apply_admin_action(&AdminActionKind::AddAdmin { admin: b }).unwrap();
+        assert!(realm.apply_admin_action(&AdminActionKind::AddAdmin { admin: b }).is_err());
+        assert!(realm.apply_admin_action(&AdminActionKind::SetThreshold { admin_threshold: 3 }).is_err());
+        realm.apply_admin_action(&AdminActionKind::SetThreshold { admin_threshold: 2 }).unwrap();
+
+        // Two admins at threshold two: removal would strand the realm, rotation is fine
+        assert!(realm.apply_admin_action(&AdminActionKind::RemoveAdmin { admin: b }).is_err());
+        realm.apply_admin_action(&AdminActionKind::RotateAdmin { old_admin: b, new_admin: c }).unwrap();
+        assert!(!realm.is_admin(&b) && realm.is_admin(&c));
+        assert_eq!(realm.admin_epoch, 2);
+        realm.apply_admin_action(&AdminActionKind::SetThreshold { admin_threshold: 1 }).unwrap();
+        assert_eq!(realm.admin_epoch, 2);
+    }
+
+    #[test]
+    fn quorum_instructions_take_threshold_signatures() {
+        let (a, b, c, outsider) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let realm = GovernanceRealm {
+            admin_wallets: [a, b, c],
+            admin_threshold: 2,
+            ..Default::default()
+        };
+        let info = |key: Pubkey, is_signer: bool| {
+            let owner = Box::leak(Box::new(Pubkey::default()));
+            AccountInfo::new(Box::leak(Box::new(key)), is_signer, false, Box::leak(Box::new(0)), &mut [], owner, false, 0)
+        };
+        let (signed_b, unsigned_c, outsider) = (info(b, true), info(c, false), info(outsider, true));
+
+        assert_eq!(realm.check_admin_quorum(&a, &[]).unwrap_err(), error!(ReputationError::AdminQuorumRequired));
+        // The same admin twice is one signature
+        assert_eq!(
+            realm.check_admin_quorum(&b, &[info(b, true)]).unwrap_err(),
+            error!(ReputationError::AdminQuorumRequired)
+        );
+        realm.check_admin_quorum(&a, &[signed_b]).unwrap();
+        assert_eq!(realm.check_admin_quorum(&a, &[unsigned_c]).unwrap_err(), error!(ReputationError::AdminRequired));
+        assert_eq!(realm.check_admin_quorum(&a, &[outsider]).unwrap_err(), error!(ReputationError::AdminRequired));
+    }
+
+    #[test]
+    fn multisig_realms_only_change_config_through_admin_actions() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut realm = GovernanceRealm {
+            admin_wallets: [a, b, Pubkey::default()],
+            admin_threshold: 1,
+            ..Default::default()
+        };
+        realm.check_direct_admin(&a).unwrap();
+        assert_eq!(realm.check_direct_admin(&Pubkey::new_unique()).unwrap_err(), error!(ReputationError::AdminRequired));
+
+        realm.admin_threshold = 2;
+        assert_eq!(realm.check_direct_admin(&a).unwrap_err(), error!(ReputationError::MultisigRequired));
+
+        // The config setters' rules hold on the AdminAction route too
+        assert!(realm
+            .apply_admin_action(&AdminActionKind::SetInteractionRetention { retention_seconds: 60 })
+            .is_err());
+        assert!(realm
+            .apply_admin_action(&AdminActionKind::SetDelegationDepth { max_delegation_depth: MAX_DELEGATION_DEPTH + 1 })
+            .is_err());
+        realm.apply_admin_action(&AdminActionKind::SetDelegationDepth { max_delegation_depth: 0 }).unwrap();
+        realm.apply_admin_action(&AdminActionKind::SetReputationScope { local_reputation_only: true }).unwrap();
+        realm
+            .apply_admin_action(&AdminActionKind::SetBridgePolicy { max_bridged_reputation: 50, attestation_authority: b })
+            .unwrap();
+        assert!(realm.local_reputation_only && realm.max_delegation_depth == 0);
+        assert_eq!((realm.max_bridged_reputation, realm.attestation_authority), (50, b));
+    }
+}
@@ -9,8 +9,29 @@ pub struct InteractionEvent {
     pub metadata_hash: [u8; 32],    // 32
     pub reputation_delta: u64,      // 8
     pub timestamp: i64,             // 8
+    pub nonce: u64,                 // 8 (sender's interaction_nonce at creation)
+    pub realm: Pubkey,              // 32 (Pubkey::default() when recorded without a realm)
+    pub realm_delta: u64,           // 8 (amount credited to the receiver's ledger; 0 without a realm)
+    pub expires_at: i64,            // 8 (closable from here; retention fixed at record time)
+    pub open_disputes: u8,          // 1 (opened and not yet dismissed)
+    pub reversed: bool,             // 1
     pub bump: u8,                   // 1
 }
 impl InteractionEvent {
-    pub const LEN: usize = 8 + 32 + 32 + 1 + 2 + 32 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 2 + 32 + 8 + 8 + 8 + 32 + 8 + 8 + 1 + 1 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct InteractionPair {
+    pub from: Pubkey,               // 32
+    pub to: Pubkey,                 // 32
+    pub recent_interactions: u32,   // 4 (within the current pair window)
+    pub window_start: i64,          // 8
+    pub last_interaction_at: i64,   // 8
+    pub total_interactions: u32,    // 4
+    pub bump: u8,                   // 1
+}
+impl InteractionPair {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 8 + 8 + 4 + 1;
 }
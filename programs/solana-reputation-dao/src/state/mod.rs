@@ -1,9 +1,25 @@
+pub mod admin_action;
+pub mod attestation;
 pub mod badge_system;
+pub mod bridge_record;
+pub mod delegation;
+pub mod dispute;
 pub mod governance_realm;
 pub mod interaction_event;
+pub mod proposal;
+pub mod realm_reputation;
 pub mod reputation_profile;
+pub mod score_history;
 
+pub use admin_action::*;
+pub use attestation::*;
 pub use badge_system::*;
+pub use bridge_record::*;
+pub use delegation::*;
+pub use dispute::*;
 pub use governance_realm::*;
 pub use interaction_event::*;
+pub use proposal::*;
+pub use realm_reputation::*;
 pub use reputation_profile::*;
+pub use score_history::*;
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::ReputationError;
+use crate::utils::constants::{VOTE_YES, VOTE_NO, VOTE_ABSTAIN};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProposalStatus {
+    #[default]
+    Active,
+    Passed,
+    Rejected,
+}
+
+#[account]
+pub struct Proposal {
+    pub realm: Pubkey,              // 32
+    pub proposer: Pubkey,           // 32
+    pub proposal_id: u64,           // 8
+    pub title: [u8; 64],            // 64
+    pub description_hash: [u8; 32], // 32
+    pub yes_votes: u64,             // 8
+    pub no_votes: u64,              // 8
+    pub abstain_votes: u64,         // 8
+    pub voter_count: u32,           // 4 (one per VoteRecord, carried delegators included)
+    pub status: ProposalStatus,     // 1
+    pub category: u8,               // 1 (CAT_* index, or PROPOSAL_CATEGORY_GENERAL)
+    pub created_at: i64,            // 8
+    pub voting_ends_at: i64,        // 8
+    pub bump: u8,                   // 1
+}
+impl Proposal {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 64 + 32 + 8 + 8 + 8 + 4 + 1 + 1 + 8 + 8 + 1;
+
+    pub fn tally_mut(&mut self, vote_type: u8) -> Result<&mut u64> {
+        match vote_type {
+            VOTE_YES => Ok(&mut self.yes_votes),
+            VOTE_NO => Ok(&mut self.no_votes),
+            VOTE_ABSTAIN => Ok(&mut self.abstain_votes),
+            _ => err!(ReputationError::InvalidVoteType),
+        }
+    }
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,           // 32
+    pub voter: Pubkey,              // 32
+    pub vote_type: u8,              // 1 (0 yes, 1 no, 2 abstain)
+    pub weight: u64,                // 8
+    pub voted_at: i64,              // 8
+    pub cast_by: Pubkey,            // 32 (voter for direct votes, else the delegate who carried it)
+    pub bump: u8,                   // 1
+}
+impl VoteRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 32 + 1;
+}
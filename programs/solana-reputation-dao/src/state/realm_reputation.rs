@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::errors::ReputationError;
+use crate::state::score_history::ScoreHistory;
+use crate::utils::constants::MAX_REALM_BADGE_BONUS;
+
+// Reputation a wallet has earned inside a single realm, created on join_realm.
+// The global ReputationProfile keeps the aggregate across all realms.
+#[account]
+pub struct RealmReputation {
+    pub realm: Pubkey,              // 32
+    pub wallet: Pubkey,             // 32
+    pub total_score: u64,           // 8
+    pub category_scores: [u64; 5],  // 40 (Dev, Gov, Community, Innovation, Security)
+    pub joined_at: i64,             // 8
+    pub last_activity: i64,         // 8
+    pub last_decay_at: i64,         // 8
+    pub score_history: ScoreHistory, // 449 (scores before recent credits, for vote weight)
+    pub badge_bonus: u64,           // 8 (live badge credit, at most MAX_REALM_BADGE_BONUS)
+    pub bump: u8,                   // 1
+}
+impl RealmReputation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 40 + 8 + 8 + 8 + ScoreHistory::LEN + 8 + 1;
+
+    // Ledger accounts are passed unseeded as optional accounts, so bind them here
+    pub fn ensure_for(&self, realm: &Pubkey, wallet: &Pubkey) -> Result<()> {
+        require_keys_eq!(self.realm, *realm, ReputationError::RealmMismatch);
+        require_keys_eq!(self.wallet, *wallet, ReputationError::RealmMismatch);
+        Ok(())
+    }
+
+    // Fills a ledger opened by join_realm or by the holder's first badge in the
+    // realm; an existing ledger keeps its history
+    pub fn open(&mut self, realm: Pubkey, wallet: Pubkey, now: i64, bump: u8) {
+        if self.wallet != Pubkey::default() {
+            return;
+        }
+        self.realm = realm;
+        self.wallet = wallet;
+        self.total_score = 0;
+        self.category_scores = [0u64; 5];
+        self.joined_at = now;
+        self.last_activity = 0;
+        self.last_decay_at = now;
+        self.score_history = ScoreHistory::default();
+        self.badge_bonus = 0;
+        self.bump = bump;
+    }
+
+    pub fn credit(&mut self, category: usize, amount: u64, now: i64) {
+        self.score_history.record(now, self.total_score, &self.category_scores);
+        self.total_score = self.total_score.saturating_add(amount);
+        self.category_scores[category] = self.category_scores[category].saturating_add(amount);
+        self.last_activity = now;
+    }
+
+    pub fn debit(&mut self, category: usize, amount: u64) {
+        self.total_score = self.total_score.saturating_sub(amount);
+        self.category_scores[category] = self.category_scores[category].saturating_sub(amount);
+    }
+
+    // Credits `amount` split across categories in the proportions of `source`
+    // scores, so bridged reputation keeps the shape it had where it was earned.
+    // A source with no category scores has no shape to follow and is rejected,
+    // so the bridge record never counts an amount nothing received.
+    pub fn credit_in_proportion(&mut self, source: &[u64; 5], amount: u64, now: i64) -> Result<()> {
+        let total = source.iter().fold(0u128, |sum, s| sum + *s as u128);
+        require!(total > 0, ReputationError::BridgeSourceEmpty);
+        let mut left = amount;
+        for (category, score) in source.iter().enumerate() {
+            let share = (amount as u128 * *score as u128 / total) as u64;
+            self.credit(category, share, now);
+            left -= share;
+        }
+        // Rounding remainder goes to the source's strongest category
+        let (top, _) = source
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, score)| **score)
+            .unwrap_or((0, &0));
+        self.credit(top, left, now);
+        Ok(())
+    }
+
+    // Credits a badge bonus up to the realm-wide badge cap and returns what
+    // was actually credited; the receipt records that amount for later debits
+    pub fn credit_badge(&mut self, category: usize, bonus: u64, now: i64) -> u64 {
+        let credited = bonus.min(MAX_REALM_BADGE_BONUS.saturating_sub(self.badge_bonus));
+        self.badge_bonus = self.badge_bonus.saturating_add(credited);
+        self.credit(category, credited, now);
+        credited
+    }
+
+    pub fn debit_badge(&mut self, category: usize, credited: u64) {
+        self.badge_bonus = self.badge_bonus.saturating_sub(credited);
+        self.debit(category, credited);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger() -> RealmReputation {
+        let mut ledger = RealmReputation {
+            realm: Pubkey::default(),
+            wallet: Pubkey::default(),
+            total_score: 0,
+            category_scores: [0; 5],
+            joined_at: 0,
+            last_activity: 0,
+            last_decay_at: 0,
+            score_history: ScoreHistory::default(),
+            badge_bonus: 0,
+            bump: 0,
+        };
+        ledger.open(Pubkey::new_unique(), Pubkey::new_unique(), 0, 255);
+        ledger
+    }
+
+    #[test]
+    fn badge_credit_stops_at_the_realm_cap() {
+        let mut ledger = ledger();
+        ledger.credit(0, 100, 0);
+        for _ in 0..3 {
+            assert_eq!(ledger.credit_badge(4, 500, 0), 500);
+        }
+        // A fourth definition adds nothing, however many the realm creates
+        assert_eq!(ledger.credit_badge(4, 500, 0), 0);
+        assert_eq!(ledger.badge_bonus, MAX_REALM_BADGE_BONUS);
+        assert_eq!(ledger.total_score, 100 + MAX_REALM_BADGE_BONUS);
+    }
+
+    #[test]
+    fn debiting_a_badge_frees_room_under_the_cap() {
+        let mut ledger = ledger();
+        assert_eq!(ledger.credit_badge(1, 1_400, 0), 1_400);
+        assert_eq!(ledger.credit_badge(1, 500, 0), 100);
+        ledger.debit_badge(1, 100);
+        assert_eq!(ledger.badge_bonus, 1_400);
+        assert_eq!(ledger.category_scores[1], 1_400);
+        assert_eq!(ledger.credit_badge(2, 50, 0), 50);
+    }
+
+    #[test]
+    fn bridged_credit_follows_the_source_categories() {
+        let mut source = ledger();
+        source.credit(0, 300, 0);
+        source.credit(4, 100, 0);
+        let mut destination = ledger();
+        destination.credit_in_proportion(&source.category_scores, 101, 7).unwrap();
+        assert_eq!(destination.category_scores, [76, 0, 0, 0, 25]);
+        assert_eq!(destination.total_score, 101);
+        assert_eq!(destination.last_activity, 7);
+
+        assert_eq!(
+            destination.credit_in_proportion(&[0; 5], 50, 8).unwrap_err(),
+            error!(ReputationError::BridgeSourceEmpty)
+        );
+        assert_eq!(destination.total_score, 101);
+    }
+}
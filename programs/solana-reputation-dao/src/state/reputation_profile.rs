@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::badge_system::Badge;
+use crate::state::score_history::ScoreHistory;
 
 #[account]
 #[derive(Default)]
@@ -8,17 +9,33 @@ pub struct ReputationProfile {
     pub total_score: u64,                  // 8
     pub category_scores: [u64; 5],         // 40 (Dev, Gov, Community, Innovation, Security)
     pub interaction_count: u32,            // 4
-    pub badges: [Badge; 10],               // 10 * 73 = 730
+    pub badges: [Badge; 10],               // 10 * 83 = 830
     pub trust_multiplier: u64,             // 8 (100 = 1.00x)
     pub last_activity: i64,                // 8
     pub reputation_decay_rate: u8,         // 1
-    pub delegated_power: u64,              // 8
-    pub delegation_received: u64,          // 8
     pub realm_memberships: [Pubkey; 5],    // 160
     pub ai_validation_score: u32,          // 4
-    pub cross_dao_reputation: u64,         // 8
+    pub cross_dao_reputation: u64,         // 8 (bridged and imported so far; those scores only credit realm ledgers)
+    pub last_decay_at: i64,                // 8
+    pub day_start: i64,                    // 8 (start of the sender's current 24h window)
+    pub interactions_in_window: u32,       // 4 (interactions sent in that window)
+    pub last_sent_at: i64,                 // 8 (last interaction sent to anyone)
+    pub interaction_nonce: u64,            // 8 (next InteractionEvent seed for this sender)
+    pub badge_count: u32,                  // 4 (inline + BadgeCollection pages)
+    pub badge_pages: u32,                  // 4 (BadgeCollection pages created)
+    pub badge_category_counts: [u16; 5],   // 10 (badges held per category)
+    pub votes_cast: u32,                   // 4 (proposal votes cast)
+    pub score_history: ScoreHistory,       // 449 (scores before recent credits, for vote weight)
+    pub last_reputation_vote_at: i64,      // 8 (cast_reputation_vote rate limit)
+    pub delegated_categories: u8,          // 1 (category bits held by this wallet's DelegationRecords)
     pub bump: u8,                          // 1
 }
 impl ReputationProfile {
-    pub const LEN: usize = 8 + 32 + 8 + 40 + 4 + 730 + 8 + 8 + 1 + 8 + 8 + 160 + 4 + 8 + 1;
-}
\ No newline at end of file
+    pub const LEN: usize = 8 + 32 + 8 + 40 + 4 + 830 + 8 + 8 + 1 + 160 + 4 + 8 + 8 + 8 + 4 + 8 + 8 + 4 + 4 + 10 + 4 + ScoreHistory::LEN + 8 + 1 + 1;
+
+    pub fn credit(&mut self, category: usize, amount: u64, now: i64) {
+        self.score_history.record(now, self.total_score, &self.category_scores);
+        self.total_score = self.total_score.saturating_add(amount);
+        self.category_scores[category] = self.category_scores[category].saturating_add(amount);
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::utils::constants::{PROPOSAL_CATEGORY_GENERAL, SCORE_HISTORY_LEN};
+
+// Scores held just before the first credit at `at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ScoreSnapshot {
+    pub at: i64,                    // 8
+    pub total: u64,                 // 8
+    pub categories: [u64; 5],       // 40
+}
+impl ScoreSnapshot {
+    pub const LEN: usize = 8 + 8 + 40;
+}
+
+// Recent credit history of a profile or ledger, oldest first, so a vote can
+// weigh reputation as it stood when the proposal was created without the
+// voter having to snapshot anything in advance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ScoreHistory {
+    pub snapshots: [ScoreSnapshot; SCORE_HISTORY_LEN], // 8 * 56
+    pub len: u8,                                       // 1
+}
+impl ScoreHistory {
+    pub const LEN: usize = ScoreSnapshot::LEN * SCORE_HISTORY_LEN + 1;
+
+    // Call before crediting. Once full, the two oldest snapshots fold into one
+    // stamped with the later time and the lower scores, so older proposals
+    // may see less weight than was held but never more.
+    pub fn record(&mut self, now: i64, total: u64, categories: &[u64; 5]) {
+        let len = self.len as usize;
+        if len > 0 && self.snapshots[len - 1].at == now {
+            return;
+        }
+        let snapshot = ScoreSnapshot { at: now, total, categories: *categories };
+        if len < SCORE_HISTORY_LEN {
+            self.snapshots[len] = snapshot;
+            self.len += 1;
+            return;
+        }
+        let oldest = self.snapshots[0];
+        let folded = &mut self.snapshots[1];
+        folded.total = folded.total.min(oldest.total);
+        for (score, older) in folded.categories.iter_mut().zip(oldest.categories) {
+            *score = (*score).min(older);
+        }
+        self.snapshots.copy_within(1.., 0);
+        self.snapshots[SCORE_HISTORY_LEN - 1] = snapshot;
+    }
+
+    // Weight on a proposal created at `created_at`: the live score in the
+    // proposal's category (or the total for general proposals), capped by what
+    // was held then. Credits in the proposal's own second count as later.
+    pub fn weight_at(&self, created_at: i64, total: u64, categories: &[u64; 5], category: u8) -> u64 {
+        let then = self.snapshots[..self.len as usize]
+            .iter()
+            .find(|snapshot| snapshot.at >= created_at);
+        if category == PROPOSAL_CATEGORY_GENERAL {
+            then.map_or(total, |snapshot| total.min(snapshot.total))
+        } else {
+            let category = category as usize;
+            then.map_or(categories[category], |snapshot| categories[category].min(snapshot.categories[category]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn weight_ignores_credits_after_the_proposal() {
+        let mut history = ScoreHistory::default();
+        history.record(NOW - 10, 0, &[0; 5]);
+        history.record(NOW + 5, 200, &[200, 0, 0, 0, 0]);
+        history.record(NOW + 5, 300, &[300, 0, 0, 0, 0]);
+        assert_eq!(history.len, 2);
+
+        let live = [500, 0, 0, 0, 0];
+        assert_eq!(history.weight_at(NOW - 20, 500, &live, PROPOSAL_CATEGORY_GENERAL), 0);
+        assert_eq!(history.weight_at(NOW, 500, &live, PROPOSAL_CATEGORY_GENERAL), 200);
+        assert_eq!(history.weight_at(NOW, 500, &live, 0), 200);
+        // Nothing credited since: the live score, which also carries any slash
+        assert_eq!(history.weight_at(NOW + 6, 150, &[150, 0, 0, 0, 0], PROPOSAL_CATEGORY_GENERAL), 150);
+        assert_eq!(history.weight_at(NOW, 150, &[150, 0, 0, 0, 0], PROPOSAL_CATEGORY_GENERAL), 150);
+    }
+
+    #[test]
+    fn full_history_folds_toward_lower_weight() {
+        let mut history = ScoreHistory::default();
+        for step in 0..SCORE_HISTORY_LEN as i64 {
+            history.record(NOW + step, 100 * step as u64, &[0; 5]);
+        }
+        // A debit between credits left the second snapshot below the first
+        history.snapshots[0].total = 50;
+        history.snapshots[1].total = 40;
+        history.record(NOW + 100, 1_000, &[0; 5]);
+
+        assert_eq!(history.len as usize, SCORE_HISTORY_LEN);
+        assert_eq!(history.snapshots[0].at, NOW + 1);
+        assert_eq!(history.snapshots[0].total, 40);
+        assert_eq!(history.weight_at(NOW - 1, 2_000, &[0; 5], PROPOSAL_CATEGORY_GENERAL), 40);
+        assert_eq!(history.weight_at(NOW + 50, 2_000, &[0; 5], PROPOSAL_CATEGORY_GENERAL), 1_000);
+    }
+}
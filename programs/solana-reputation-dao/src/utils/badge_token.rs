@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{metadata_pointer, ExtensionType},
+    instruction::AuthorityType,
+    state::Mint,
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+use crate::errors::ReputationError;
+
+pub const BADGE_TOKEN_SYMBOL: &str = "BADGE";
+
+// Accounts needed to create and mint a soulbound badge token
+pub struct BadgeTokenAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub holder: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub holder_token_account: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+// Creates a Token-2022 mint with NonTransferable, PermanentDelegate,
+// MintCloseAuthority and an embedded TokenMetadata, mints exactly one token to
+// the holder's ATA and drops the mint authority. The program's badge authority
+// PDA stays the permanent delegate and close authority so revocation can burn
+// the token and free the mint address for a later claim.
+pub fn mint_soulbound_badge(
+    accounts: &BadgeTokenAccounts,
+    mint_seeds: &[&[u8]],
+    authority_seeds: &[&[u8]],
+    name: String,
+    uri: String,
+    additional_metadata: Vec<(String, String)>,
+) -> Result<()> {
+    let token_program_id = accounts.token_program.key;
+    let mint_key = accounts.mint.key;
+    let authority_key = accounts.authority.key;
+    require_keys_eq!(
+        accounts.holder_token_account.key(),
+        get_associated_token_address_with_program_id(accounts.holder.key, mint_key, token_program_id),
+        ReputationError::InvalidBadgeToken
+    );
+
+    let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MintCloseAuthority,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let metadata = TokenMetadata {
+        update_authority: Some(*authority_key).try_into()?,
+        mint: *mint_key,
+        name: name.clone(),
+        symbol: BADGE_TOKEN_SYMBOL.to_string(),
+        uri: uri.clone(),
+        additional_metadata: additional_metadata.clone(),
+    };
+    // Token-2022 reallocs for the metadata TLV itself, but the rent must already be there
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata.tlv_size_of()?);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            accounts.payer.key,
+            mint_key,
+            lamports,
+            mint_len as u64,
+            token_program_id,
+        ),
+        &[accounts.payer.clone(), accounts.mint.clone(), accounts.system_program.clone()],
+        &[mint_seeds],
+    )?;
+
+    invoke(
+        &spl_token_2022::instruction::initialize_non_transferable_mint(token_program_id, mint_key)?,
+        std::slice::from_ref(accounts.mint),
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_permanent_delegate(
+            token_program_id,
+            mint_key,
+            authority_key,
+        )?,
+        std::slice::from_ref(accounts.mint),
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint_close_authority(
+            token_program_id,
+            mint_key,
+            Some(authority_key),
+        )?,
+        std::slice::from_ref(accounts.mint),
+    )?;
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program_id,
+            mint_key,
+            Some(*authority_key),
+            Some(*mint_key),
+        )?,
+        std::slice::from_ref(accounts.mint),
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program_id,
+            mint_key,
+            authority_key,
+            None,
+            0,
+        )?,
+        std::slice::from_ref(accounts.mint),
+    )?;
+
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program_id,
+            mint_key,
+            authority_key,
+            mint_key,
+            authority_key,
+            name,
+            BADGE_TOKEN_SYMBOL.to_string(),
+            uri,
+        ),
+        &[accounts.mint.clone(), accounts.authority.clone()],
+        &[authority_seeds],
+    )?;
+    for (key, value) in additional_metadata {
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_field(
+                token_program_id,
+                mint_key,
+                authority_key,
+                Field::Key(key),
+                value,
+            ),
+            &[accounts.mint.clone(), accounts.authority.clone()],
+            &[authority_seeds],
+        )?;
+    }
+
+    // The holder keeps an empty ATA for this mint address after a revocation
+    associated_token::create_idempotent(CpiContext::new(
+        accounts.associated_token_program.clone(),
+        associated_token::Create {
+            payer: accounts.payer.clone(),
+            associated_token: accounts.holder_token_account.clone(),
+            authority: accounts.holder.clone(),
+            mint: accounts.mint.clone(),
+            system_program: accounts.system_program.clone(),
+            token_program: accounts.token_program.clone(),
+        },
+    ))?;
+
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_program_id,
+            mint_key,
+            accounts.holder_token_account.key,
+            authority_key,
+            &[],
+            1,
+        )?,
+        &[
+            accounts.mint.clone(),
+            accounts.holder_token_account.clone(),
+            accounts.authority.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::set_authority(
+            token_program_id,
+            mint_key,
+            None,
+            AuthorityType::MintTokens,
+            authority_key,
+            &[],
+        )?,
+        &[accounts.mint.clone(), accounts.authority.clone()],
+        &[authority_seeds],
+    )?;
+
+    Ok(())
+}
+
+// Burns the holder's badge token through the permanent delegate, then closes
+// the emptied mint and returns its rent to `destination`
+pub fn burn_soulbound_badge<'info>(
+    token_program: &AccountInfo<'info>,
+    holder: &Pubkey,
+    mint: &AccountInfo<'info>,
+    holder_token_account: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    require_keys_eq!(
+        holder_token_account.key(),
+        get_associated_token_address_with_program_id(holder, mint.key, token_program.key),
+        ReputationError::InvalidBadgeToken
+    );
+
+    invoke_signed(
+        &spl_token_2022::instruction::burn(
+            token_program.key,
+            holder_token_account.key,
+            mint.key,
+            authority.key,
+            &[],
+            1,
+        )?,
+        &[holder_token_account.clone(), mint.clone(), authority.clone()],
+        &[authority_seeds],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::close_account(
+            token_program.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[mint.clone(), destination.clone(), authority.clone()],
+        &[authority_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
@@ -1,11 +1,46 @@
-pub const MAX_BADGES: u8 = 10;
+pub const MAX_BADGES: u8 = 10; // stored inline on the profile, the rest go to BadgeCollection pages
+pub const BADGES_PER_COLLECTION: usize = 16;
 pub const DEFAULT_TRUST_MULTIPLIER: u64 = 100; // 1.00x
 pub const DEFAULT_DECAY_RATE: u8 = 2; // monthly 2%
-pub const BADGE_SCORE_BONUS: u64 = 25;
+pub const MAX_DECAY_RATE: u8 = 20; // monthly, cap on a realm's decay_factor
+pub const MAX_CATEGORY_WEIGHT: u16 = 300; // 3.00x, cap on a realm's per-category algorithm weight
+pub const MAX_CROSS_REALM_FACTOR: u8 = 100;
+pub const DECAY_GRACE_PERIOD: i64 = 7 * 86_400; // inactivity before decay kicks in
+pub const PAIR_WINDOW_SECONDS: i64 = 7 * 86_400; // window for per-pair frequency penalty
+pub const MIN_SEND_INTERVAL: i64 = 30; // between any two interactions from one sender
+pub const DEFAULT_INTERACTION_RETENTION: u32 = 30 * 86_400; // before an event's rent can be reclaimed
+pub const DISPUTE_WINDOW: i64 = 14 * 86_400; // after an interaction, disputes can no longer be opened
+pub const MIN_INTERACTION_RETENTION: u32 = DISPUTE_WINDOW as u32; // events outlive their dispute window
+pub const DISPUTE_MIN_REPUTATION: u64 = 1000; // members at or above this may dispute others' interactions
+pub const MAX_TRUST_SLASH: u64 = 100; // trust_multiplier points removable by one reversal
+pub const DEFAULT_MAX_BRIDGED_REPUTATION: u64 = 500; // per realm, until the admin sets a bridge policy
+pub const BRIDGE_COOLDOWN_SECONDS: i64 = 86_400; // between bridges from the same source realm
+pub const MAX_DELEGATION_DEPTH: u8 = 8; // longest delegation chain, and cap on a realm's vote-time depth
+pub const DEFAULT_DELEGATION_DEPTH: u8 = 3;
+pub const DELEGATION_GENERAL_BIT: u8 = 1 << 5; // after the five category bits, for general proposals
+pub const DELEGATION_MASK_ALL: u8 = 0b11_1111; // every category bit plus DELEGATION_GENERAL_BIT
+pub const MAX_ATTESTATION_VALIDITY: u32 = 7 * 86_400; // exported attestations must be imported within this
+pub const ADMIN_ACTION_TTL: i64 = 7 * 86_400; // proposed admin actions must be approved and executed within this
+pub const REPUTATION_VOTE_COOLDOWN: i64 = 86_400; // between reputation votes from one wallet
+pub const SCORE_HISTORY_LEN: usize = 8; // credit snapshots kept per profile and ledger for vote weighting
 
 // Category indices
 pub const CAT_DEV: usize = 0;
 pub const CAT_GOV: usize = 1;
 pub const CAT_COMM: usize = 2;
 pub const CAT_INNOV: usize = 3;
-pub const CAT_SEC: usize = 4;
\ No newline at end of file
+pub const CAT_SEC: usize = 4;
+pub const PROPOSAL_CATEGORY_GENERAL: u8 = u8::MAX; // proposal not tied to one category
+
+// Proposal vote choices
+pub const VOTE_YES: u8 = 0;
+pub const VOTE_NO: u8 = 1;
+pub const VOTE_ABSTAIN: u8 = 2;
+pub const MAX_PROPOSAL_TITLE_LEN: usize = 64;
+
+// Badge definitions
+pub const MAX_BADGE_NAME_LEN: usize = 32;
+pub const MAX_BADGE_URI_LEN: usize = 128;
+pub const MAX_BADGE_SCORE_BONUS: u64 = 500;
+pub const MIN_AUTO_AWARD_SCORE: u64 = 100; // category reputation auto-award criteria must require in total
+pub const MAX_REALM_BADGE_BONUS: u64 = 1_500; // live badge credit one ledger can hold across all of a realm's definitions
@@ -1,3 +1,4 @@
+pub mod badge_token;
 pub mod constants;
 pub mod reputation_math;
 pub mod security;
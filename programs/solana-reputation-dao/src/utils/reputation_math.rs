@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::state::ReputationProfile;
+use crate::state::{ReputationProfile, GovernanceRealm, RealmReputation};
 use crate::errors::ReputationError;
+use crate::utils::constants::*;
 
 pub fn calculate_interaction_impact(
     from_profile: &ReputationProfile,
     to_profile: &ReputationProfile,
+    realm: Option<&GovernanceRealm>,
     interaction_type: u8,
     weight: u16,
+    now: i64,
 ) -> Result<u64> {
     let base_delta: u64 = match interaction_type {
         0 => 10,
@@ -22,11 +25,17 @@ pub fn calculate_interaction_impact(
         _ => return err!(ReputationError::InvalidInteractionType),
     };
 
-    let weighted_delta = (base_delta.saturating_mul(weight as u64)) / 100;
+    let mut weighted_delta = (base_delta.saturating_mul(weight as u64)) / 100;
+
+    if let Some(realm) = realm {
+        let category = interaction_category(interaction_type);
+        let category_weight = realm.reputation_algorithm.category_weight(category) as u64;
+        weighted_delta = weighted_delta.saturating_mul(category_weight) / 100;
+    }
 
     let giver_multiplier = influence_multiplier(from_profile.total_score);
     let receiver_resistance = resistance_factor(to_profile.total_score);
-    let time_bonus = activity_bonus(from_profile.last_activity)?;
+    let time_bonus = activity_bonus(from_profile.last_activity, now);
 
     let mut final_delta = weighted_delta
         .saturating_mul(giver_multiplier)
@@ -40,6 +49,18 @@ pub fn calculate_interaction_impact(
     Ok(final_delta)
 }
 
+pub fn interaction_category(interaction_type: u8) -> usize {
+    match interaction_type {
+        3 | 4 => CAT_DEV,           // Development
+        2 | 5 | 8 => CAT_COMM,      // Community/Mentorship
+        1 => CAT_COMM,              // Comment -> Community
+        6 => CAT_SEC,               // Security
+        7 => CAT_INNOV,             // Innovation
+        9 => CAT_GOV,               // Governance-ish
+        _ => CAT_COMM,
+    }
+}
+
 fn influence_multiplier(reputation: u64) -> u64 {
     match reputation {
         0..=100 => 80,
@@ -60,46 +81,211 @@ fn resistance_factor(reputation: u64) -> u64 {
     }
 }
 
-fn activity_bonus(last_activity: i64) -> Result<u64> {
-    let current_time = Clock::get()?.unix_timestamp;
-    let elapsed = current_time.saturating_sub(last_activity);
-    let bonus = match elapsed {
+fn activity_bonus(last_activity: i64, now: i64) -> u64 {
+    match now.saturating_sub(last_activity) {
         0..=3600 => 110,
         3601..=86400 => 105,
         86401..=604800 => 100,
         604801..=2592000 => 95,
         _ => 85,
-    };
-    Ok(bonus)
+    }
 }
 
+// Decays every full day of inactivity past the grace period that hasn't been
+// decayed yet, and advances last_decay_at so repeated calls never double count.
+// Returns the amount removed from total_score.
 pub fn calculate_reputation_decay(
     profile: &mut ReputationProfile,
+    rate: u8,
+    current_timestamp: i64,
+) -> Result<u64> {
+    decay_scores(
+        &mut profile.total_score,
+        &mut profile.category_scores,
+        profile.last_activity,
+        &mut profile.last_decay_at,
+        rate,
+        current_timestamp,
+    )
+}
+
+// Same schedule applied to a realm-local ledger
+pub fn calculate_realm_reputation_decay(
+    ledger: &mut RealmReputation,
+    rate: u8,
+    current_timestamp: i64,
+) -> Result<u64> {
+    decay_scores(
+        &mut ledger.total_score,
+        &mut ledger.category_scores,
+        ledger.last_activity,
+        &mut ledger.last_decay_at,
+        rate,
+        current_timestamp,
+    )
+}
+
+fn decay_scores(
+    total_score: &mut u64,
+    category_scores: &mut [u64; 5],
+    last_activity: i64,
+    last_decay_at: &mut i64,
+    rate: u8,
     current_timestamp: i64,
-) -> Result<()> {
-    let elapsed = current_timestamp.saturating_sub(profile.last_activity);
-    let days = (elapsed / 86_400).max(0);
-    if days > 7 {
-        let rate = profile.reputation_decay_rate as u64;
-        let decay_total = profile.total_score
+) -> Result<u64> {
+    let window_start = last_activity
+        .saturating_add(DECAY_GRACE_PERIOD)
+        .max(*last_decay_at);
+    let days = (current_timestamp.saturating_sub(window_start) / 86_400).max(0);
+    if days == 0 {
+        return Ok(0);
+    }
+
+    let rate = rate as u64;
+    let decay_total = total_score
+        .saturating_mul(rate)
+        .saturating_mul(days as u64)
+        / (100 * 30);
+    *total_score = total_score.saturating_sub(decay_total);
+
+    for score in category_scores.iter_mut() {
+        let category_decay = score
             .saturating_mul(rate)
             .saturating_mul(days as u64)
             / (100 * 30);
-        profile.total_score = profile.total_score.saturating_sub(decay_total);
-
-        for i in 0..5 {
-            let category_decay = profile.category_scores[i]
-                .saturating_mul(rate)
-                .saturating_mul(days as u64)
-                / (100 * 30);
-            profile.category_scores[i] = profile.category_scores[i].saturating_sub(category_decay);
-        }
+        *score = score.saturating_sub(category_decay);
     }
-    Ok(())
+
+    *last_decay_at = window_start
+        .checked_add(days.checked_mul(86_400).ok_or(ReputationError::MathOverflow)?)
+        .ok_or(ReputationError::MathOverflow)?;
+    Ok(decay_total)
+}
+
+// Score a realm gates on: the realm-local ledger when the realm opts into
+// local reputation, otherwise the global profile
+pub fn realm_scoped_score(
+    profile: &ReputationProfile,
+    realm: &GovernanceRealm,
+    ledger: Option<&RealmReputation>,
+) -> Result<u64> {
+    if !realm.local_reputation_only {
+        return Ok(profile.total_score);
+    }
+    let ledger = ledger.ok_or(ReputationError::RealmReputationRequired)?;
+    ledger.ensure_for(&realm.realm_id, &profile.wallet)?;
+    Ok(ledger.total_score)
 }
 
 pub fn hash_metadata(metadata: &str) -> [u8; 32] {
     use anchor_lang::solana_program::keccak;
     let hash = keccak::hash(metadata.as_bytes());
     hash.to_bytes()
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn profile(total_score: u64, last_activity: i64) -> ReputationProfile {
+        ReputationProfile {
+            wallet: Pubkey::new_unique(),
+            total_score,
+            last_activity,
+            ..Default::default()
+        }
+    }
+
+    fn realm_with_community_weight(weight: u16) -> GovernanceRealm {
+        let mut realm = GovernanceRealm::default();
+        realm.reputation_algorithm.community_weight = weight;
+        realm
+    }
+
+    #[test]
+    fn interaction_impact_is_capped() {
+        let delta = calculate_interaction_impact(&profile(0, NOW), &profile(0, 0), None, 0, 1000, NOW).unwrap();
+        assert_eq!(delta, 200);
+    }
+
+    #[test]
+    fn interaction_impact_applies_influence_resistance_and_activity() {
+        // Comment at weight 20 is worth 1 point before multipliers
+        let fresh = calculate_interaction_impact(&profile(0, NOW), &profile(0, 0), None, 1, 20, NOW).unwrap();
+        assert_eq!(fresh, 110); // 1 * 80 * 110 / 80
+
+        let stale_giver = profile(6_000, NOW - 40 * 86_400);
+        let veteran = calculate_interaction_impact(&stale_giver, &profile(6_000, 0), None, 1, 20, NOW).unwrap();
+        assert_eq!(veteran, 75); // 1 * 160 * 85 / 180
+    }
+
+    #[test]
+    fn realm_weights_scale_the_category() {
+        let giver = profile(6_000, NOW - 40 * 86_400);
+        let receiver = profile(6_000, 0);
+        let realm = realm_with_community_weight(200);
+
+        let weighted = calculate_interaction_impact(&giver, &receiver, Some(&realm), 1, 20, NOW).unwrap();
+        assert_eq!(weighted, 151); // 2 * 160 * 85 / 180
+
+        // Zero-weighted categories earn nothing in that realm
+        let muted = calculate_interaction_impact(&giver, &receiver, Some(&realm_with_community_weight(0)), 1, 20, NOW)
+            .unwrap();
+        assert_eq!(muted, 0);
+    }
+
+    #[test]
+    fn interaction_impact_rejects_unknown_types() {
+        assert!(calculate_interaction_impact(&profile(0, NOW), &profile(0, 0), None, 10, 100, NOW).is_err());
+    }
+
+    #[test]
+    fn decay_waits_for_grace_period() {
+        let mut p = profile(3_000, NOW);
+        p.last_decay_at = NOW;
+        let decayed = calculate_reputation_decay(&mut p, 2, NOW + DECAY_GRACE_PERIOD + 86_399).unwrap();
+        assert_eq!(decayed, 0);
+        assert_eq!(p.total_score, 3_000);
+        assert_eq!(p.last_decay_at, NOW);
+    }
+
+    #[test]
+    fn decay_counts_each_day_once() {
+        let mut p = profile(3_000, NOW);
+        p.last_decay_at = NOW;
+        p.category_scores = [1_500, 0, 1_500, 0, 0];
+        let start = NOW + DECAY_GRACE_PERIOD;
+
+        // 2% a month over 10 days: 3000 * 2 * 10 / 3000
+        assert_eq!(calculate_reputation_decay(&mut p, 2, start + 10 * 86_400 + 5).unwrap(), 20);
+        assert_eq!(p.total_score, 2_980);
+        assert_eq!(p.category_scores, [1_490, 0, 1_490, 0, 0]);
+        assert_eq!(p.last_decay_at, start + 10 * 86_400);
+
+        // Calling again within the same day removes nothing more
+        assert_eq!(calculate_reputation_decay(&mut p, 2, start + 10 * 86_400 + 600).unwrap(), 0);
+        assert_eq!(calculate_reputation_decay(&mut p, 2, start + 11 * 86_400).unwrap(), 1);
+    }
+
+    #[test]
+    fn realm_ledger_decays_on_its_own_schedule() {
+        let mut ledger = RealmReputation {
+            realm: Pubkey::new_unique(),
+            wallet: Pubkey::new_unique(),
+            total_score: 600,
+            category_scores: [600, 0, 0, 0, 0],
+            joined_at: NOW,
+            last_activity: NOW,
+            last_decay_at: NOW,
+            score_history: Default::default(),
+            badge_bonus: 0,
+            bump: 0,
+        };
+        let at = NOW + DECAY_GRACE_PERIOD + 30 * 86_400;
+        assert_eq!(calculate_realm_reputation_decay(&mut ledger, 10, at).unwrap(), 60);
+        assert_eq!(ledger.total_score, 540);
+        assert_eq!(ledger.category_scores[0], 540);
+        assert_eq!(calculate_realm_reputation_decay(&mut ledger, 10, at).unwrap(), 0);
+    }
+}
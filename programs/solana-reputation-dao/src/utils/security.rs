@@ -1,20 +1,33 @@
 use anchor_lang::prelude::*;
-use crate::state::{reputation_profile::ReputationProfile, governance_realm::GovernanceRealm};
+use crate::state::{
+    reputation_profile::ReputationProfile,
+    governance_realm::GovernanceRealm,
+    interaction_event::InteractionPair,
+    realm_reputation::RealmReputation,
+};
 use crate::errors::ReputationError;
+use crate::utils::constants::*;
+use crate::utils::reputation_math;
 
 pub fn validate_interaction_limits(
     from_profile: &ReputationProfile,
     to_profile: &ReputationProfile,
+    pair: &InteractionPair,
     interaction_type: u8,
     current_timestamp: i64,
-    cooldown_period: i64,
 ) -> Result<()> {
-    // Cooldown except for high reputation users
-    let since = current_timestamp.saturating_sub(from_profile.last_activity);
-    require!(
-        since >= cooldown_period || from_profile.total_score > 1000,
-        ReputationError::CooldownActive
+    // Sender-wide floor, so spreading interactions over many receivers still paces out
+    let since_sent = current_timestamp.saturating_sub(from_profile.last_sent_at);
+    require!(since_sent >= MIN_SEND_INTERVAL, ReputationError::CooldownActive);
+
+    // Per (from, to) cooldown that grows with how often this pair interacts
+    let cooldown_period = calculate_dynamic_cooldown(
+        base_cooldown(interaction_type)?,
+        recent_pair_interactions(pair, current_timestamp),
+        from_profile.total_score,
     );
+    let since = current_timestamp.saturating_sub(pair.last_interaction_at);
+    require!(since >= cooldown_period, ReputationError::CooldownActive);
 
     // Prevent self-interaction
     require!(from_profile.wallet != to_profile.wallet, ReputationError::SelfInteractionForbidden);
@@ -30,6 +43,30 @@ pub fn validate_interaction_limits(
     Ok(())
 }
 
+fn base_cooldown(interaction_type: u8) -> Result<i64> {
+    match interaction_type {
+        0..=2 => Ok(300),
+        3..=6 => Ok(1800),
+        7..=9 => Ok(7200),
+        _ => err!(ReputationError::InvalidInteractionType),
+    }
+}
+
+fn recent_pair_interactions(pair: &InteractionPair, current_timestamp: i64) -> u32 {
+    let elapsed = current_timestamp.saturating_sub(pair.window_start);
+    if elapsed >= PAIR_WINDOW_SECONDS { 0 } else { pair.recent_interactions }
+}
+
+pub fn record_pair_interaction(pair: &mut InteractionPair, current_timestamp: i64) {
+    if current_timestamp.saturating_sub(pair.window_start) >= PAIR_WINDOW_SECONDS {
+        pair.window_start = current_timestamp;
+        pair.recent_interactions = 0;
+    }
+    pair.recent_interactions = pair.recent_interactions.saturating_add(1);
+    pair.total_interactions = pair.total_interactions.saturating_add(1);
+    pair.last_interaction_at = current_timestamp;
+}
+
 fn calculate_daily_limit(reputation: u64) -> u32 {
     match reputation {
         0..=100 => 5,
@@ -40,10 +77,23 @@ fn calculate_daily_limit(reputation: u64) -> u32 {
     }
 }
 
+const SECONDS_IN_DAY: i64 = 86_400;
+
+// Interactions the profile has sent in its current 24h window
 fn count_daily_interactions(profile: &ReputationProfile, current_timestamp: i64) -> u32 {
-    let seconds_in_day = 86_400;
-    let elapsed = current_timestamp.saturating_sub(profile.last_activity);
-    if elapsed > seconds_in_day { 0 } else { profile.interaction_count % 100 }
+    let elapsed = current_timestamp.saturating_sub(profile.day_start);
+    if elapsed >= SECONDS_IN_DAY { 0 } else { profile.interactions_in_window }
+}
+
+// Counts one sent interaction, opening a fresh window once the last one expired,
+// and starts the sender-wide interval
+pub fn record_daily_interaction(profile: &mut ReputationProfile, current_timestamp: i64) {
+    if current_timestamp.saturating_sub(profile.day_start) >= SECONDS_IN_DAY {
+        profile.day_start = current_timestamp;
+        profile.interactions_in_window = 0;
+    }
+    profile.interactions_in_window = profile.interactions_in_window.saturating_add(1);
+    profile.last_sent_at = current_timestamp;
 }
 
 fn validate_interaction_permissions(profile: &ReputationProfile, interaction_type: u8) -> Result<()> {
@@ -58,7 +108,10 @@ fn validate_interaction_permissions(profile: &ReputationProfile, interaction_typ
             Ok(())
         },
         9 => {
-            require!(profile.total_score >= 1000 && profile.cross_dao_reputation > 0, ReputationError::InsufficientReputation);
+            // Imports no longer touch the global profile, so cross-DAO standing is
+            // membership in more than one realm
+            let realms = profile.realm_memberships.iter().filter(|m| **m != Pubkey::default()).count();
+            require!(profile.total_score >= 1000 && realms > 1, ReputationError::InsufficientReputation);
             Ok(())
         },
         _ => Err(error!(ReputationError::InvalidInteractionType)),
@@ -69,9 +122,11 @@ fn validate_interaction_permissions(profile: &ReputationProfile, interaction_typ
 pub fn authorize_realm_action(
     profile: &ReputationProfile,
     realm: &GovernanceRealm,
+    ledger: Option<&RealmReputation>,
     action_type: u8, // 0 vote, 1 propose, 2 admin
 ) -> Result<()> {
-    require!(profile.total_score >= realm.min_reputation_threshold, ReputationError::InsufficientReputation);
+    let score = reputation_math::realm_scoped_score(profile, realm, ledger)?;
+    require!(score >= realm.min_reputation_threshold, ReputationError::InsufficientReputation);
 
     // membership check (simplified)
     let is_member = profile.realm_memberships.contains(&realm.realm_id);
@@ -80,7 +135,7 @@ pub fn authorize_realm_action(
     match action_type {
         0 => Ok(()),
         1 => {
-            require!(profile.total_score >= realm.min_reputation_threshold.saturating_mul(2), ReputationError::InsufficientReputation);
+            require!(score >= realm.min_reputation_threshold.saturating_mul(2), ReputationError::InsufficientReputation);
             Ok(())
         },
         2 => {
@@ -109,4 +164,108 @@ pub fn calculate_dynamic_cooldown(base_cooldown: i64, recent_interactions: u32,
     };
 
     (base_cooldown * frequency_penalty * reputation_factor) / 10_000
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_START: i64 = 1_700_000_000;
+
+    fn sender_with_window(sent: u32) -> ReputationProfile {
+        ReputationProfile {
+            wallet: Pubkey::new_unique(),
+            day_start: DAY_START,
+            interactions_in_window: sent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn daily_count_holds_until_window_expires() {
+        let sender = sender_with_window(4);
+        assert_eq!(count_daily_interactions(&sender, DAY_START), 4);
+        assert_eq!(count_daily_interactions(&sender, DAY_START + SECONDS_IN_DAY - 1), 4);
+        assert_eq!(count_daily_interactions(&sender, DAY_START + SECONDS_IN_DAY), 0);
+    }
+
+    #[test]
+    fn record_rolls_window_at_day_boundary() {
+        let mut sender = sender_with_window(4);
+        record_daily_interaction(&mut sender, DAY_START + SECONDS_IN_DAY - 1);
+        assert_eq!(sender.day_start, DAY_START);
+        assert_eq!(sender.interactions_in_window, 5);
+
+        record_daily_interaction(&mut sender, DAY_START + SECONDS_IN_DAY);
+        assert_eq!(sender.day_start, DAY_START + SECONDS_IN_DAY);
+        assert_eq!(sender.interactions_in_window, 1);
+    }
+
+    #[test]
+    fn limit_is_enforced_against_sender_activity_only() {
+        // Low reputation senders get 5 interactions a day
+        let sender = sender_with_window(5);
+        let mut receiver = sender_with_window(0);
+
+        let pair = InteractionPair::default();
+
+        let before_boundary = DAY_START + SECONDS_IN_DAY - 1;
+        assert_eq!(
+            validate_interaction_limits(&sender, &receiver, &pair, 0, before_boundary).unwrap_err(),
+            error!(ReputationError::DailyLimitExceeded)
+        );
+        assert!(validate_interaction_limits(&sender, &receiver, &pair, 0, DAY_START + SECONDS_IN_DAY).is_ok());
+
+        // A busy receiver does not eat into the sender's allowance
+        let fresh_sender = sender_with_window(0);
+        receiver.interaction_count = 99;
+        assert!(validate_interaction_limits(&fresh_sender, &receiver, &pair, 0, before_boundary).is_ok());
+    }
+
+    #[test]
+    fn pair_cooldown_grows_with_recent_interactions() {
+        let sender = sender_with_window(0);
+        let receiver = sender_with_window(0);
+        let mut pair = InteractionPair::default();
+        for _ in 0..6 {
+            record_pair_interaction(&mut pair, DAY_START);
+        }
+
+        // Six recent interactions put the pair on a 1.5x cooldown (450s for type 0)
+        assert_eq!(
+            validate_interaction_limits(&sender, &receiver, &pair, 0, DAY_START + 449).unwrap_err(),
+            error!(ReputationError::CooldownActive)
+        );
+        assert!(validate_interaction_limits(&sender, &receiver, &pair, 0, DAY_START + 450).is_ok());
+
+        // The penalty resets once the pair window rolls over
+        record_pair_interaction(&mut pair, DAY_START + PAIR_WINDOW_SECONDS);
+        assert_eq!(pair.recent_interactions, 1);
+        assert_eq!(pair.total_interactions, 7);
+    }
+
+    #[test]
+    fn sender_interval_spans_all_receivers() {
+        let mut sender = sender_with_window(0);
+        let receiver = sender_with_window(0);
+        record_daily_interaction(&mut sender, DAY_START);
+
+        // A fresh pair does not bypass the sender-wide interval
+        let pair = InteractionPair::default();
+        assert_eq!(
+            validate_interaction_limits(&sender, &receiver, &pair, 0, DAY_START + MIN_SEND_INTERVAL - 1).unwrap_err(),
+            error!(ReputationError::CooldownActive)
+        );
+        assert!(validate_interaction_limits(&sender, &receiver, &pair, 0, DAY_START + MIN_SEND_INTERVAL).is_ok());
+    }
+
+    #[test]
+    fn cross_dao_interactions_need_two_realm_memberships() {
+        let mut profile = ReputationProfile { total_score: 1_000, ..Default::default() };
+        profile.realm_memberships[0] = Pubkey::new_unique();
+        assert!(validate_interaction_permissions(&profile, 9).is_err());
+        profile.realm_memberships[3] = Pubkey::new_unique();
+        assert!(validate_interaction_permissions(&profile, 9).is_ok());
+        profile.total_score = 999;
+        assert!(validate_interaction_permissions(&profile, 9).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{pda, process_instruction, send};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt};
+use solana_reputation_dao::state::{BadgeCriteria, BadgeDefinitionArgs, BadgeEligibility, BadgeReceipt};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_reputation_dao::{accounts, instruction, ID};
+
+const REALM_NAME: &str = "realm-badge-token-lifecycle";
+
+struct Badge {
+    realm: Pubkey,
+    definition: Pubkey,
+    profile: Pubkey,
+    receipt: Pubkey,
+    ledger: Pubkey,
+    mint: Pubkey,
+    holder_token_account: Pubkey,
+    authority: Pubkey,
+}
+
+impl Badge {
+    fn new(holder: &Pubkey) -> Self {
+        let realm = pda(&[b"realm", REALM_NAME.as_bytes()]);
+        let definition = pda(&[b"badge_def", realm.as_ref(), &0u16.to_le_bytes()]);
+        let receipt = pda(&[b"badge", holder.as_ref(), definition.as_ref()]);
+        let mint = pda(&[b"badge_mint", receipt.as_ref()]);
+        Self {
+            realm,
+            definition,
+            profile: pda(&[b"reputation", holder.as_ref()]),
+            receipt,
+            ledger: pda(&[b"realm_rep", realm.as_ref(), holder.as_ref()]),
+            mint,
+            holder_token_account: get_associated_token_address_with_program_id(
+                holder,
+                &mint,
+                &spl_token_2022::ID,
+            ),
+            authority: pda(&[b"badge_authority"]),
+        }
+    }
+
+    fn claim(&self, holder: &Pubkey, issuer: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ClaimBadge {
+                profile: self.profile,
+                badge_account: self.receipt,
+                realm: self.realm,
+                badge_definition: self.definition,
+                issuer: Some(*issuer),
+                badge_collection: None,
+                realm_reputation: self.ledger,
+                badge_mint: Some(self.mint),
+                holder_token_account: Some(self.holder_token_account),
+                badge_authority: Some(self.authority),
+                token_program: Some(spl_token_2022::ID),
+                associated_token_program: Some(anchor_spl::associated_token::ID),
+                user: *holder,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimBadge { proof_hash: [9; 32], merkle_proof: vec![] }.data(),
+        }
+    }
+
+    fn revoke(&self, holder: &Pubkey, admin: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::RevokeBadge {
+                realm: self.realm,
+                badge_account: self.receipt,
+                badge_definition: self.definition,
+                profile: self.profile,
+                badge_collection: None,
+                realm_reputation: self.ledger,
+                badge_mint: Some(self.mint),
+                holder_token_account: Some(self.holder_token_account),
+                badge_authority: Some(self.authority),
+                token_program: Some(spl_token_2022::ID),
+                owner: *holder,
+                admin: *admin,
+            }
+            .to_account_metas(None),
+            data: instruction::RevokeBadge {}.data(),
+        }
+    }
+}
+
+async fn token_balance(banks: &mut BanksClient, address: Pubkey) -> u64 {
+    let account = banks.get_account(address).await.unwrap().unwrap();
+    let base = &account.data[..spl_token_2022::state::Account::LEN];
+    spl_token_2022::state::Account::unpack(base).unwrap().amount
+}
+
+#[tokio::test]
+async fn revoked_badge_token_can_be_claimed_again() {
+    let program_test = ProgramTest::new("solana_reputation_dao", ID, processor!(process_instruction));
+    let (mut banks, payer, _) = program_test.start().await;
+    let admin = Keypair::new();
+    let holder = Keypair::new();
+    let badge = Badge::new(&holder.pubkey());
+
+    let fund = [admin.pubkey(), holder.pubkey()]
+        .map(|to| solana_sdk::system_instruction::transfer(&payer.pubkey(), &to, 1_000_000_000));
+    send(&mut banks, &payer, &fund, &[]).await;
+
+    let setup = [
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CreateRealm {
+                realm: badge.realm,
+                admin: admin.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CreateRealm {
+                realm_name: REALM_NAME.to_string(),
+                algorithm_weights: [100; 5],
+            }
+            .data(),
+        },
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CreateBadgeDefinition {
+                realm: badge.realm,
+                badge_definition: badge.definition,
+                admin: admin.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CreateBadgeDefinition {
+                args: BadgeDefinitionArgs {
+                    badge_type: 4,
+                    name: "Security Auditor".to_string(),
+                    uri: String::new(),
+                    score_bonus: 25,
+                    category: 4,
+                    max_supply: 0,
+                    expires_at: 0,
+                    validity_seconds: 0,
+                    eligibility: BadgeEligibility::default(),
+                    criteria: BadgeCriteria::default(),
+                    issuer: admin.pubkey(),
+                    merkle_root: [0; 32],
+                },
+            }
+            .data(),
+        },
+        Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeProfile {
+                profile: badge.profile,
+                user: holder.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeProfile {}.data(),
+        },
+    ];
+    send(&mut banks, &payer, &setup, &[&admin, &holder]).await;
+
+    let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+    let claim = [budget.clone(), badge.claim(&holder.pubkey(), &admin.pubkey())];
+    send(&mut banks, &payer, &claim, &[&holder, &admin]).await;
+    assert_eq!(token_balance(&mut banks, badge.holder_token_account).await, 1);
+    let receipt = banks.get_account(badge.receipt).await.unwrap().unwrap();
+    let receipt = BadgeReceipt::try_deserialize(&mut &receipt.data[..]).unwrap();
+    assert_eq!(receipt.token_mint, badge.mint);
+
+    send(&mut banks, &payer, &[badge.revoke(&holder.pubkey(), &admin.pubkey())], &[&admin]).await;
+    assert!(banks.get_account(badge.receipt).await.unwrap().is_none());
+    assert!(banks.get_account(badge.mint).await.unwrap().is_none());
+    assert_eq!(token_balance(&mut banks, badge.holder_token_account).await, 0);
+
+    // The same receipt and mint addresses are derived again, in a fresh transaction
+    let blockhash = banks.get_latest_blockhash().await.unwrap();
+    banks.get_new_latest_blockhash(&blockhash).await.unwrap();
+    send(&mut banks, &payer, &claim, &[&holder, &admin]).await;
+    assert_eq!(token_balance(&mut banks, badge.holder_token_account).await, 1);
+    assert!(banks.get_account(badge.mint).await.unwrap().is_some());
+}
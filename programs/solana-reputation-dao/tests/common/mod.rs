@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use solana_program_test::{BanksClient, ProgramTestContext};
+use solana_reputation_dao::ID;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+// Anchor's entrypoint wants accounts that outlive the call
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    solana_reputation_dao::entry(program_id, accounts, data)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+pub async fn send(banks: &mut BanksClient, payer: &Keypair, ixs: &[Instruction], signers: &[&Keypair]) {
+    let blockhash = banks.get_latest_blockhash().await.unwrap();
+    let mut all = vec![payer];
+    all.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all, blockhash);
+    banks.process_transaction(tx).await.unwrap();
+}
+
+// Votes weigh scores held before the proposal's second, so tests step past it
+#[allow(dead_code)] // not every test binary moves the clock
+pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
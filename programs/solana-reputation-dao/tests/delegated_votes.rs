@@ -0,0 +1,248 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use common::{advance_clock, pda, process_instruction, send};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestContext};
+use solana_reputation_dao::state::{Proposal, VoteRecord};
+use solana_reputation_dao::utils::constants::{CAT_COMM, DELEGATION_GENERAL_BIT, PROPOSAL_CATEGORY_GENERAL};
+use solana_reputation_dao::{accounts, instruction, ID};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+const REALM_NAME: &str = "realm-delegated-votes";
+
+fn profile(user: &Pubkey) -> Pubkey {
+    pda(&[b"reputation", user.as_ref()])
+}
+
+fn delegation(delegator: &Pubkey, delegatee: &Pubkey) -> Pubkey {
+    pda(&[b"delegation", delegator.as_ref(), delegatee.as_ref()])
+}
+
+fn vote_record(proposal: &Pubkey, voter: &Pubkey) -> Pubkey {
+    pda(&[b"vote", proposal.as_ref(), voter.as_ref()])
+}
+
+// One max-weight endorsement is worth the capped 200 points
+fn endorse(from: &Pubkey, to: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::RecordInteraction {
+            from_profile: profile(from),
+            to_profile: profile(to),
+            interaction_event: pda(&[b"interaction", from.as_ref(), to.as_ref(), &0u64.to_le_bytes()]),
+            interaction_pair: pda(&[b"pair", from.as_ref(), to.as_ref()]),
+            from_user: *from,
+            to_user: *to,
+            realm: None,
+            to_realm_reputation: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RecordInteraction {
+            interaction_type: 0,
+            weight: 1000,
+            metadata: "endorse".to_string(),
+        }
+        .data(),
+    }
+}
+
+fn delegate(delegator: &Pubkey, delegatee: &Pubkey, weight_percentage: u8, category_mask: u8) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DelegateReputation {
+            delegator_profile: profile(delegator),
+            delegatee_profile: profile(delegatee),
+            delegation_record: delegation(delegator, delegatee),
+            previous_record: None,
+            delegator: *delegator,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::DelegateReputation {
+            delegate_to: *delegatee,
+            weight_percentage,
+            category_mask,
+            expires_at: 0,
+        }
+        .data(),
+    }
+}
+
+fn propose(realm: &Pubkey, proposal: &Pubkey, proposer: &Pubkey, category: u8) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateProposal {
+            realm: *realm,
+            proposer_profile: profile(proposer),
+            proposer_realm_reputation: None,
+            proposal: *proposal,
+            proposer: *proposer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateProposal {
+            title: "Treasury policy".to_string(),
+            description: "Adopt the new treasury policy".to_string(),
+            category,
+        }
+        .data(),
+    }
+}
+
+fn cast_vote(realm: &Pubkey, proposal: &Pubkey, voter: &Pubkey, vote_type: u8, carried: &[Pubkey]) -> Instruction {
+    let mut metas = accounts::CastVote {
+        realm: *realm,
+        proposal: *proposal,
+        voter_profile: profile(voter),
+        voter_realm_reputation: None,
+        vote_record: vote_record(proposal, voter),
+        voter: *voter,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for delegator in carried {
+        metas.push(AccountMeta::new_readonly(delegation(delegator, voter), false));
+        metas.push(AccountMeta::new_readonly(profile(delegator), false));
+        metas.push(AccountMeta::new(vote_record(proposal, delegator), false));
+    }
+    Instruction { program_id: ID, accounts: metas, data: instruction::CastVote { vote_type }.data() }
+}
+
+async fn proposal_state(banks: &mut BanksClient, address: Pubkey) -> Proposal {
+    let account = banks.get_account(address).await.unwrap().unwrap();
+    Proposal::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+async fn vote_state(banks: &mut BanksClient, address: Pubkey) -> VoteRecord {
+    let account = banks.get_account(address).await.unwrap().unwrap();
+    VoteRecord::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+// A realm with Alice and Bob as members, each holding 200 Community points
+// from endorsing the other, and the clock moved past those credits
+async fn setup() -> (ProgramTestContext, BanksClient, Keypair, Pubkey, Keypair, Keypair) {
+    let program_test = ProgramTest::new("solana_reputation_dao", ID, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let mut banks = context.banks_client.clone();
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let realm = pda(&[b"realm", REALM_NAME.as_bytes()]);
+
+    let fund = [admin.pubkey(), alice.pubkey(), bob.pubkey()]
+        .map(|to| system_instruction::transfer(&payer.pubkey(), &to, 1_000_000_000));
+    send(&mut banks, &payer, &fund, &[]).await;
+
+    let mut setup = vec![Instruction {
+        program_id: ID,
+        accounts: accounts::CreateRealm { realm, admin: admin.pubkey(), system_program: system_program::ID }
+            .to_account_metas(None),
+        data: instruction::CreateRealm { realm_name: REALM_NAME.to_string(), algorithm_weights: [100; 5] }.data(),
+    }];
+    for user in [alice.pubkey(), bob.pubkey()] {
+        setup.push(Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeProfile {
+                profile: profile(&user),
+                user,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeProfile {}.data(),
+        });
+    }
+    setup.push(endorse(&alice.pubkey(), &bob.pubkey()));
+    setup.push(endorse(&bob.pubkey(), &alice.pubkey()));
+    send(&mut banks, &payer, &setup, &[&admin, &alice, &bob]).await;
+
+    let mut membership = Vec::new();
+    for user in [alice.pubkey(), bob.pubkey()] {
+        membership.push(Instruction {
+            program_id: ID,
+            accounts: accounts::JoinRealm {
+                realm,
+                profile: profile(&user),
+                realm_reputation: pda(&[b"realm_rep", realm.as_ref(), user.as_ref()]),
+                member: user,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::JoinRealm {}.data(),
+        });
+    }
+    send(&mut banks, &payer, &membership, &[&alice, &bob]).await;
+    advance_clock(&mut context, 1).await;
+    (context, banks, payer, realm, alice, bob)
+}
+
+#[tokio::test]
+async fn carried_vote_record_survives_prefunding_and_counts_once() {
+    let (_context, mut banks, payer, realm, alice, bob) = setup().await;
+    let proposal = pda(&[b"proposal", realm.as_ref(), &0u64.to_le_bytes()]);
+
+    // Bob hands half of his general weight to Alice, then Alice proposes
+    let delegate = delegate(&bob.pubkey(), &alice.pubkey(), 50, DELEGATION_GENERAL_BIT);
+    let propose = propose(&realm, &proposal, &alice.pubkey(), PROPOSAL_CATEGORY_GENERAL);
+    send(&mut banks, &payer, &[delegate, propose], &[&alice, &bob]).await;
+
+    // Lamports sent to Bob's vote record address ahead of time must not block it
+    let bob_record = vote_record(&proposal, &bob.pubkey());
+    send(&mut banks, &payer, &[system_instruction::transfer(&payer.pubkey(), &bob_record, 1_000_000)], &[]).await;
+
+    let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+    let carried = cast_vote(&realm, &proposal, &alice.pubkey(), 0, &[bob.pubkey()]);
+    send(&mut banks, &payer, &[budget.clone(), carried], &[&alice]).await;
+
+    let state = proposal_state(&mut banks, proposal).await;
+    assert_eq!(state.yes_votes, 200 + 100);
+    assert_eq!(state.voter_count, 2);
+    assert_eq!(banks.get_account(bob_record).await.unwrap().unwrap().owner, ID);
+    let record = vote_state(&mut banks, bob_record).await;
+    assert_eq!(record.cast_by, alice.pubkey());
+    assert_eq!(record.weight, 100);
+
+    // Bob overriding his delegate moves his weight but is not a new voter
+    let direct = cast_vote(&realm, &proposal, &bob.pubkey(), 1, &[]);
+    send(&mut banks, &payer, &[budget, direct], &[&bob]).await;
+    let state = proposal_state(&mut banks, proposal).await;
+    assert_eq!(state.yes_votes, 200);
+    assert_eq!(state.no_votes, 200);
+    assert_eq!(state.voter_count, 2);
+}
+
+#[tokio::test]
+async fn full_delegation_carries_the_delegators_direct_weight() {
+    let (_context, mut banks, payer, realm, alice, bob) = setup().await;
+    let community = pda(&[b"proposal", realm.as_ref(), &0u64.to_le_bytes()]);
+    let general = pda(&[b"proposal", realm.as_ref(), &1u64.to_le_bytes()]);
+
+    // One record hands all of Bob's Community and general weight to Alice
+    let mask = (1 << CAT_COMM) | DELEGATION_GENERAL_BIT;
+    let setup = [
+        delegate(&bob.pubkey(), &alice.pubkey(), 100, mask),
+        propose(&realm, &community, &alice.pubkey(), CAT_COMM as u8),
+        propose(&realm, &general, &alice.pubkey(), PROPOSAL_CATEGORY_GENERAL),
+    ];
+    send(&mut banks, &payer, &setup, &[&alice, &bob]).await;
+
+    let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+    for proposal in [community, general] {
+        let carried = cast_vote(&realm, &proposal, &alice.pubkey(), 0, &[bob.pubkey()]);
+        send(&mut banks, &payer, &[budget.clone(), carried], &[&alice]).await;
+        let carried = vote_state(&mut banks, vote_record(&proposal, &bob.pubkey())).await.weight;
+
+        let direct = cast_vote(&realm, &proposal, &bob.pubkey(), 0, &[]);
+        send(&mut banks, &payer, &[budget.clone(), direct], &[&bob]).await;
+        let record = vote_state(&mut banks, vote_record(&proposal, &bob.pubkey())).await;
+        assert_eq!(record.cast_by, bob.pubkey());
+        assert_eq!(record.weight, 200);
+        assert_eq!(carried, record.weight);
+        assert_eq!(proposal_state(&mut banks, proposal).await.yes_votes, 400);
+    }
+}
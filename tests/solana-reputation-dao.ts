@@ -15,6 +15,28 @@ describe("Advanced Reputation Scoreboard", () => {
   let userAProfile: anchor.web3.PublicKey;
   let userBProfile: anchor.web3.PublicKey;
   let testRealm: anchor.web3.PublicKey;
+  let pairAB: anchor.web3.PublicKey;
+  let pairAA: anchor.web3.PublicKey;
+
+  const interactionEventPda = (
+    from: anchor.web3.PublicKey,
+    to: anchor.web3.PublicKey,
+    nonce: number
+  ) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("interaction"),
+        from.toBuffer(),
+        to.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  const userARealmReputation = () =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm_rep"), testRealm.toBuffer(), userA.publicKey.toBuffer()],
+      program.programId
+    )[0];
 
   before(async () => {
     userA = anchor.web3.Keypair.generate();
@@ -48,6 +70,16 @@ describe("Advanced Reputation Scoreboard", () => {
       [Buffer.from("realm"), Buffer.from("test-realm")],
       program.programId
     );
+
+    [pairAB] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pair"), userA.publicKey.toBuffer(), userB.publicKey.toBuffer()],
+      program.programId
+    );
+
+    [pairAA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pair"), userA.publicKey.toBuffer(), userA.publicKey.toBuffer()],
+      program.programId
+    );
   });
 
   describe("Profile Initialization", () => {
@@ -102,19 +134,20 @@ describe("Advanced Reputation Scoreboard", () => {
     });
 
     it("Should record basic interaction and update reputation", async () => {
-      const interactionPda = anchor.web3.Keypair.generate();
+      const interactionPda = interactionEventPda(userA.publicKey, userB.publicKey, 0);
 
       await program.methods
         .recordInteraction(2, 150, "Helpful code review")
         .accounts({
           fromProfile: userAProfile,
           toProfile: userBProfile,
-          interactionEvent: interactionPda.publicKey,
+          interactionEvent: interactionPda,
+          interactionPair: pairAB,
           fromUser: userA.publicKey,
           toUser: userB.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([userA, interactionPda])
+        .signers([userA])
         .rpc();
 
       const toProfile: any = await program.account.reputationProfile.fetch(
@@ -122,23 +155,28 @@ describe("Advanced Reputation Scoreboard", () => {
       );
       expect(toProfile.totalScore.toNumber()).to.be.greaterThan(0);
       expect(toProfile.interactionCount).to.equal(1);
+
+      const event: any = await program.account.interactionEvent.fetch(interactionPda);
+      expect(event.nonce.toNumber()).to.equal(0);
+      expect(event.to.toString()).to.equal(userB.publicKey.toString());
     });
 
     it("Should enforce cooldown periods", async () => {
       try {
-        const interactionPda2 = anchor.web3.Keypair.generate();
+        const interactionPda2 = interactionEventPda(userA.publicKey, userB.publicKey, 1);
 
         await program.methods
           .recordInteraction(2, 100, "Another review")
           .accounts({
             fromProfile: userAProfile,
             toProfile: userBProfile,
-            interactionEvent: interactionPda2.publicKey,
+            interactionEvent: interactionPda2,
+            interactionPair: pairAB,
             fromUser: userA.publicKey,
             toUser: userB.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
-          .signers([userA, interactionPda2])
+          .signers([userA])
           .rpc();
 
         expect.fail("Should have enforced cooldown");
@@ -149,19 +187,20 @@ describe("Advanced Reputation Scoreboard", () => {
 
     it("Should prevent self-interaction", async () => {
       try {
-        const selfInteractionPda = anchor.web3.Keypair.generate();
+        const selfInteractionPda = interactionEventPda(userA.publicKey, userA.publicKey, 1);
 
         await program.methods
           .recordInteraction(1, 100, "Self vote")
           .accounts({
             fromProfile: userAProfile,
             toProfile: userAProfile,
-            interactionEvent: selfInteractionPda.publicKey,
+            interactionEvent: selfInteractionPda,
+            interactionPair: pairAA,
             fromUser: userA.publicKey,
             toUser: userA.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
-          .signers([userA, selfInteractionPda])
+          .signers([userA])
           .rpc();
 
         expect.fail("Should have prevented self-interaction");
@@ -190,26 +229,92 @@ describe("Advanced Reputation Scoreboard", () => {
       expect(realm.reputationAlgorithm.innovationWeight).to.equal(175);
     });
 
+    const delegationPda = (delegator: anchor.web3.PublicKey, delegatee: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegation"), delegator.toBuffer(), delegatee.toBuffer()],
+        program.programId
+      )[0];
+    const GENERAL = 1 << 5;
+    const delegate = (
+      from: anchor.web3.Keypair,
+      fromProfile: anchor.web3.PublicKey,
+      to: anchor.web3.Keypair,
+      toProfile: anchor.web3.PublicKey,
+      percentage: number,
+      categoryMask: number,
+      onward: anchor.web3.PublicKey[] = []
+    ) =>
+      program.methods
+        .delegateReputation(to.publicKey, percentage, categoryMask, new anchor.BN(0))
+        .accounts({
+          delegatorProfile: fromProfile,
+          delegateeProfile: toProfile,
+          delegationRecord: delegationPda(from.publicKey, to.publicKey),
+          previousRecord: null,
+          delegator: from.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(onward.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+        .signers([from])
+        .rpc();
+
     it("Should handle reputation delegation", async () => {
+      // userB's community score comes from userA's code review
+      await delegate(userB, userBProfile, userA, userAProfile, 25, 1 << 2); // Delegate 25% of Community
+
+      // Only the percentage is stored; votes work out the power from the delegator's score
+      const record: any = await program.account.delegationRecord.fetch(delegationPda(userB.publicKey, userA.publicKey));
+      expect(record.categoryMask).to.equal(1 << 2);
+      expect(record.weightPercentage).to.equal(25);
+      expect(record.delegatee.toString()).to.equal(userA.publicKey.toString());
+      const profile: any = await program.account.reputationProfile.fetch(userBProfile);
+      expect(profile.delegatedCategories).to.equal(1 << 2);
+    });
+
+    it("Should reject delegation cycles in any category of the mask", async () => {
+      try {
+        // userB already delegates Community to userA
+        await delegate(userA, userAProfile, userB, userBProfile, 10, (1 << 4) | (1 << 2), [
+          delegationPda(userB.publicKey, userA.publicKey),
+        ]);
+        expect.fail("cycle accepted");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DelegationCycle");
+      }
+      // userB has no Security delegation, so the chain ends at userB
+      await delegate(userA, userAProfile, userB, userBProfile, 10, 1 << 4);
+    });
+
+    it("Should replace a delegation when delegating to the same delegatee again", async () => {
+      await delegate(userB, userBProfile, userA, userAProfile, 50, GENERAL | (1 << 1));
+
+      const general: any = await program.account.delegationRecord.fetch(delegationPda(userB.publicKey, userA.publicKey));
+      expect(general.categoryMask).to.equal(GENERAL | (1 << 1));
+      expect(general.weightPercentage).to.equal(50);
+      // Community is free again; one record now covers Governance and general proposals
+      const profile: any = await program.account.reputationProfile.fetch(userBProfile);
+      expect(profile.delegatedCategories).to.equal(GENERAL | (1 << 1));
+    });
+
+    it("Should revoke a delegation and close its record", async () => {
+      const delegationRecord = delegationPda(userB.publicKey, userA.publicKey);
       await program.methods
-        .delegateReputation(userB.publicKey, 25) // Delegate 25% voting power
+        .revokeDelegation()
         .accounts({
-          delegatorProfile: userAProfile,
-          delegateeProfile: userBProfile,
-          delegator: userA.publicKey,
+          delegationRecord,
+          delegatorProfile: userBProfile,
+          delegator: userB.publicKey,
+          authority: userB.publicKey,
         })
-        .signers([userA])
+        .signers([userB])
         .rpc();
 
-      const delegatorProfile: any =
-        await program.account.reputationProfile.fetch(userAProfile);
-      const delegateeProfile: any =
-        await program.account.reputationProfile.fetch(userBProfile);
-
-      expect(delegatorProfile.delegatedPower.toNumber()).to.be.greaterThan(0);
-      expect(delegateeProfile.delegationReceived.toNumber()).to.be.greaterThan(
-        0
-      );
+      expect(await provider.connection.getAccountInfo(delegationRecord)).to.be.null;
+      const profile: any = await program.account.reputationProfile.fetch(userBProfile);
+      expect(profile.delegatedCategories).to.equal(0);
+      expect(
+        await provider.connection.getAccountInfo(delegationPda(userA.publicKey, userB.publicKey))
+      ).to.not.be.null;
     });
   });
 
@@ -222,20 +327,56 @@ describe("Advanced Reputation Scoreboard", () => {
     });
 
     it("Should handle badge claims with proof validation", async () => {
-      const badgePda = anchor.web3.Keypair.generate();
+      const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("badge_def"), testRealm.toBuffer(), Buffer.from([0, 0])],
+        program.programId
+      );
+      const [badgePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("badge"), userA.publicKey.toBuffer(), badgeDefinition.toBuffer()],
+        program.programId
+      );
       const proofHash = Array.from(
         Buffer.from("test-proof-hash".repeat(2), "utf8")
       ).slice(0, 32);
 
+      // Realm admin acts as the Developer badge issuer
       await program.methods
-        .claimBadge(1, proofHash) // Developer badge
+        .createBadgeDefinition({
+          badgeType: 1,
+          name: "Developer",
+          uri: "",
+          scoreBonus: new anchor.BN(25),
+          category: 0,
+          maxSupply: 0,
+          expiresAt: new anchor.BN(0),
+          validitySeconds: new anchor.BN(0),
+          eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+          criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+          issuer: admin.publicKey,
+          merkleRoot: Array(32).fill(0),
+        })
+        .accounts({
+          realm: testRealm,
+          badgeDefinition,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .claimBadge(proofHash, []) // Developer badge
         .accounts({
           profile: userAProfile,
-          badgeAccount: badgePda.publicKey,
+          badgeAccount: badgePda,
+          realm: testRealm,
+          badgeDefinition,
+          issuer: admin.publicKey,
+          realmReputation: userARealmReputation(),
           user: userA.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([userA, badgePda])
+        .signers([userA, admin])
         .rpc();
 
       const profile: any = await program.account.reputationProfile.fetch(
@@ -243,6 +384,60 @@ describe("Advanced Reputation Scoreboard", () => {
       );
       // First badge should be non-zero type
       expect(profile.badges[0].badgeType).to.not.equal(0);
+      expect(profile.badges[0].issuerRealm.toString()).to.equal(testRealm.toString());
+      const definition: any = await program.account.badgeDefinition.fetch(badgeDefinition);
+      expect(definition.supply).to.equal(1);
+      // The bonus lands in the issuing realm's ledger only
+      const ledger: any = await program.account.realmReputation.fetch(userARealmReputation());
+      expect(ledger.categoryScores[0].toNumber()).to.be.at.least(25);
+    });
+
+    it("Should let realm admins revoke a badge", async () => {
+      const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("badge_def"), testRealm.toBuffer(), Buffer.from([0, 0])],
+        program.programId
+      );
+      const [badgePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("badge"), userA.publicKey.toBuffer(), badgeDefinition.toBuffer()],
+        program.programId
+      );
+      // Definitions with no validity period never lapse
+      try {
+        await program.methods
+          .expireBadges()
+          .accounts({ profile: userAProfile, caller: admin.publicKey })
+          .remainingAccounts([
+            { pubkey: badgePda, isWritable: true, isSigner: false },
+            { pubkey: userARealmReputation(), isWritable: true, isSigner: false },
+          ])
+          .signers([admin])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("BadgeNotExpired");
+      }
+
+      const before: any = await program.account.realmReputation.fetch(userARealmReputation());
+
+      await program.methods
+        .revokeBadge()
+        .accounts({
+          realm: testRealm,
+          badgeAccount: badgePda,
+          badgeDefinition,
+          profile: userAProfile,
+          realmReputation: userARealmReputation(),
+          owner: userA.publicKey,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      const after: any = await program.account.reputationProfile.fetch(userAProfile);
+      expect(after.badges[0].badgeType).to.deep.equal({ none: {} });
+      const ledger: any = await program.account.realmReputation.fetch(userARealmReputation());
+      expect(ledger.totalScore.toNumber()).to.equal(before.totalScore.toNumber() - 25);
+      expect(await provider.connection.getAccountInfo(badgePda)).to.be.null;
     });
   });
 
@@ -262,7 +457,8 @@ describe("Advanced Reputation Scoreboard", () => {
           .accounts({
             fromProfile: userAProfile,
             toProfile: userBProfile,
-            interactionEvent: anchor.web3.Keypair.generate().publicKey,
+            interactionEvent: interactionEventPda(userA.publicKey, userB.publicKey, 1),
+            interactionPair: pairAB,
             fromUser: userA.publicKey,
             toUser: userB.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -279,5 +475,53 @@ describe("Advanced Reputation Scoreboard", () => {
     it("Should enable cross-realm reputation sharing (placeholder)", async () => {
       expect(true).to.be.true;
     });
+
+    it("Should reject bridging reputation never earned in the source realm", async () => {
+      const [destRealm] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("realm"), Buffer.from("bridge-dest")],
+        program.programId
+      );
+      const [sourceReputation] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("realm_rep"), testRealm.toBuffer(), userA.publicKey.toBuffer()],
+        program.programId
+      );
+      const [bridgeRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("bridge"), userAProfile.toBuffer(), testRealm.toBuffer(), destRealm.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createRealm("bridge-dest", [100, 100, 100, 100, 100])
+        .accounts({
+          realm: destRealm,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      try {
+        await program.methods
+          .bridgeReputation(50, new anchor.BN(0))
+          .accounts({
+            profile: userAProfile,
+            sourceRealm: testRealm,
+            sourceReputation,
+            bridgeRecord,
+            realm: destRealm,
+            destinationReputation: anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("realm_rep"), destRealm.toBuffer(), userA.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            user: userA.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([userA])
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("AccountNotInitialized");
+      }
+    });
   });
 });
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+describe("Badge Collections Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  const pda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const admin = anchor.web3.Keypair.generate();
+  const holder = anchor.web3.Keypair.generate();
+  const name = "realm-badge-pages";
+  const realm = pda([Buffer.from("realm"), Buffer.from(name)]);
+  const profile = pda([Buffer.from("reputation"), holder.publicKey.toBuffer()]);
+  const realmReputation = pda([Buffer.from("realm_rep"), realm.toBuffer(), holder.publicKey.toBuffer()]);
+  const page0 = pda([Buffer.from("badge_collection"), holder.publicKey.toBuffer(), Buffer.from([0, 0, 0, 0])]);
+  const definitionOf = (id: number) => {
+    const seed = Buffer.alloc(2);
+    seed.writeUInt16LE(id);
+    return pda([Buffer.from("badge_def"), realm.toBuffer(), seed]);
+  };
+  const receiptOf = (id: number) =>
+    pda([Buffer.from("badge"), holder.publicKey.toBuffer(), definitionOf(id).toBuffer()]);
+
+  const claim = (id: number, badgeCollection: anchor.web3.PublicKey | null) =>
+    program.methods
+      .claimBadge(Array(32).fill(id), [])
+      .accounts({
+        profile,
+        badgeAccount: receiptOf(id),
+        realm,
+        badgeDefinition: definitionOf(id),
+        issuer: admin.publicKey,
+        badgeCollection,
+        realmReputation,
+        user: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([holder, admin])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [admin, holder]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 5e9),
+        "confirmed"
+      );
+    }
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .initializeProfile()
+      .accounts({ profile, user: holder.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([holder])
+      .rpc();
+
+    // One more definition than fits inline
+    for (let id = 0; id <= 10; id++) {
+      await program.methods
+        .createBadgeDefinition({
+          badgeType: 10,
+          name: `Badge ${id}`,
+          uri: "",
+          scoreBonus: new anchor.BN(5),
+          category: id % 5,
+          maxSupply: 0,
+          expiresAt: new anchor.BN(0),
+          validitySeconds: new anchor.BN(0),
+          eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+          criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+          issuer: admin.publicKey,
+          merkleRoot: Array(32).fill(0),
+        })
+        .accounts({
+          realm,
+          badgeDefinition: definitionOf(id),
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    }
+  });
+
+  it("Claims into the inline slots first", async () => {
+    for (let id = 0; id < 10; id++) {
+      await claim(id, null);
+    }
+    const acc: any = await program.account.reputationProfile.fetch(profile);
+    expect(acc.badgeCount).to.equal(10);
+    expect(acc.badges.every((b: any) => !("none" in b.badgeType))).to.be.true;
+    const ledger: any = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.totalScore.toNumber()).to.equal(50);
+  });
+
+  it("Needs an overflow page once the inline slots are full", async () => {
+    try {
+      await claim(10, null);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("BadgeStorageFull");
+    }
+
+    await program.methods
+      .createBadgeCollection()
+      .accounts({
+        profile,
+        badgeCollection: page0,
+        user: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+    const page: any = await program.account.badgeCollection.fetch(page0);
+    expect(page.owner.toBase58()).to.equal(holder.publicKey.toBase58());
+    expect(page.page).to.equal(0);
+    const acc: any = await program.account.reputationProfile.fetch(profile);
+    expect(acc.badgePages).to.equal(1);
+
+    await claim(10, page0);
+    const filled: any = await program.account.badgeCollection.fetch(page0);
+    expect(filled.badges[0].definitionId).to.equal(10);
+    const after: any = await program.account.reputationProfile.fetch(profile);
+    expect(after.badgeCount).to.equal(11);
+  });
+
+  it("Rejects a second claim through the receipt PDA, inline or paged", async () => {
+    // The receipt already exists, so neither claim gets past account creation
+    for (const id of [0, 10]) {
+      try {
+        await claim(id, page0);
+        expect.fail("Should have failed");
+      } catch (e: any) {
+        expect(String(e.logs)).to.include("already in use");
+      }
+    }
+    const acc: any = await program.account.reputationProfile.fetch(profile);
+    expect(acc.badgeCount).to.equal(11);
+  });
+});
@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+describe("Badge Expiry and Renewal Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  const pda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const profileOf = (kp: anchor.web3.Keypair) => pda([Buffer.from("reputation"), kp.publicKey.toBuffer()]);
+
+  const [admin, holder, sponsor] = [0, 1, 2].map(() => anchor.web3.Keypair.generate());
+  const name = "realm-badge-expiry";
+  const realm = pda([Buffer.from("realm"), Buffer.from(name)]);
+  const ledger = pda([Buffer.from("realm_rep"), realm.toBuffer(), holder.publicKey.toBuffer()]);
+  const badgeDefinition = pda([Buffer.from("badge_def"), realm.toBuffer(), Buffer.from([0, 0])]);
+  const badge = pda([Buffer.from("badge"), holder.publicKey.toBuffer(), badgeDefinition.toBuffer()]);
+
+  const membership = (joined: boolean) =>
+    joined
+      ? program.methods
+          .joinRealm()
+          .accounts({
+            realm,
+            profile: profileOf(holder),
+            realmReputation: ledger,
+            member: holder.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([holder])
+          .rpc()
+      : program.methods
+          .leaveRealm()
+          .accounts({ realm, profile: profileOf(holder), member: holder.publicKey })
+          .signers([holder])
+          .rpc();
+
+  const renew = () =>
+    program.methods
+      .renewBadge(Array(32).fill(7), [])
+      .accounts({
+        realm,
+        badgeDefinition,
+        badgeAccount: badge,
+        profile: profileOf(holder),
+        badgeCollection: null,
+        realmReputation: ledger,
+        issuer: admin.publicKey,
+        user: holder.publicKey,
+      })
+      .signers([holder, admin])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [admin, holder, sponsor]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 2e9),
+        "confirmed"
+      );
+    }
+    for (const kp of [holder, sponsor]) {
+      await program.methods
+        .initializeProfile()
+        .accounts({ profile: profileOf(kp), user: kp.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([kp])
+        .rpc();
+    }
+    // Enough reputation to join the realm
+    await program.methods
+      .recordInteraction(0, 1000, "endorse")
+      .accounts({
+        fromProfile: profileOf(sponsor),
+        toProfile: profileOf(holder),
+        interactionEvent: pda([
+          Buffer.from("interaction"),
+          sponsor.publicKey.toBuffer(),
+          holder.publicKey.toBuffer(),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+        ]),
+        interactionPair: pda([Buffer.from("pair"), sponsor.publicKey.toBuffer(), holder.publicKey.toBuffer()]),
+        fromUser: sponsor.publicKey,
+        toUser: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    await membership(true);
+
+    await program.methods
+      .createBadgeDefinition({
+        badgeType: 3,
+        name: "Core Member",
+        uri: "",
+        scoreBonus: new anchor.BN(40),
+        category: 2,
+        maxSupply: 0,
+        expiresAt: new anchor.BN(0),
+        validitySeconds: new anchor.BN(1),
+        eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: true },
+        criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+        issuer: admin.publicKey,
+        merkleRoot: Array(32).fill(0),
+      })
+      .accounts({ realm, badgeDefinition, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .claimBadge(Array(32).fill(7), [])
+      .accounts({
+        profile: profileOf(holder),
+        badgeAccount: badge,
+        realm,
+        badgeDefinition,
+        issuer: admin.publicKey,
+        badgeCollection: null,
+        realmReputation: ledger,
+        user: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([holder, admin])
+      .rpc();
+  });
+
+  it("Debits the issuing realm's ledger when the badge lapses", async () => {
+    const before: any = await program.account.realmReputation.fetch(ledger);
+    expect(before.categoryScores[2].toNumber()).to.equal(40);
+
+    await new Promise((resolve) => setTimeout(resolve, 2500));
+    await program.methods
+      .expireBadges()
+      .accounts({ profile: profileOf(holder), caller: sponsor.publicKey })
+      .remainingAccounts([
+        { pubkey: badge, isWritable: true, isSigner: false },
+        { pubkey: ledger, isWritable: true, isSigner: false },
+      ])
+      .signers([sponsor])
+      .rpc();
+
+    const receipt: any = await program.account.badgeReceipt.fetch(badge);
+    expect(receipt.lapsed).to.be.true;
+    const after: any = await program.account.realmReputation.fetch(ledger);
+    expect(after.categoryScores[2].toNumber()).to.equal(0);
+    expect(after.badgeBonus.toNumber()).to.equal(0);
+  });
+
+  it("Only renews for holders who still meet the eligibility rules", async () => {
+    await membership(false);
+    try {
+      await renew();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("BadgeEligibilityNotMet");
+    }
+
+    await membership(true);
+    await renew();
+    const receipt: any = await program.account.badgeReceipt.fetch(badge);
+    expect(receipt.lapsed).to.be.false;
+    const ledgerAfter: any = await program.account.realmReputation.fetch(ledger);
+    expect(ledgerAfter.categoryScores[2].toNumber()).to.equal(40);
+  });
+});
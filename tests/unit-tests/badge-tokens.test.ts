@@ -0,0 +1,172 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+const TOKEN_2022_PROGRAM_ID = new anchor.web3.PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new anchor.web3.PublicKey(
+  "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+);
+
+describe("Badge Tokens Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  it("Mints a soulbound badge token on claim, burns it on revoke and mints again on re-claim", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const a = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(a.publicKey, 2e9),
+      "confirmed"
+    );
+
+    const name = "realm-badge-token";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    const [aP] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), a.publicKey.toBuffer()],
+      program.programId
+    );
+    const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_def"), realm.toBuffer(), Buffer.from([0, 0])],
+      program.programId
+    );
+    const [badge] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge"), a.publicKey.toBuffer(), badgeDefinition.toBuffer()],
+      program.programId
+    );
+    const [badgeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_mint"), badge.toBuffer()],
+      program.programId
+    );
+    const [badgeAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_authority")],
+      program.programId
+    );
+    const [realmReputation] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm_rep"), realm.toBuffer(), a.publicKey.toBuffer()],
+      program.programId
+    );
+    const [holderTokenAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [a.publicKey.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), badgeMint.toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .createBadgeDefinition({
+        badgeType: 4,
+        name: "Security Auditor",
+        uri: "",
+        scoreBonus: new anchor.BN(25),
+        category: 4,
+        maxSupply: 0,
+        expiresAt: new anchor.BN(0),
+        validitySeconds: new anchor.BN(0),
+        eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+        criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+        issuer: admin.publicKey,
+        merkleRoot: Array(32).fill(0),
+      })
+      .accounts({
+        realm,
+        badgeDefinition,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeProfile()
+      .accounts({
+        profile: aP,
+        user: a.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([a])
+      .rpc();
+
+    const claim = () =>
+      program.methods
+        .claimBadge(Array(32).fill(9), [])
+        .accounts({
+          profile: aP,
+          badgeAccount: badge,
+          realm,
+          badgeDefinition,
+          issuer: admin.publicKey,
+          badgeCollection: null,
+          realmReputation,
+          badgeMint,
+          holderTokenAccount,
+          badgeAuthority,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          user: a.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([
+          anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        ])
+        .signers([a, admin])
+        .rpc();
+    await claim();
+
+    const receipt: any = await program.account.badgeReceipt.fetch(badge);
+    expect(receipt.tokenMint.toString()).to.equal(badgeMint.toString());
+    let balance = await provider.connection.getTokenAccountBalance(holderTokenAccount);
+    expect(balance.value.amount).to.equal("1");
+    let ledger: any = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.categoryScores[4].toNumber()).to.equal(25);
+
+    await program.methods
+      .revokeBadge()
+      .accounts({
+        realm,
+        badgeAccount: badge,
+        badgeDefinition,
+        profile: aP,
+        badgeCollection: null,
+        realmReputation,
+        badgeMint,
+        holderTokenAccount,
+        badgeAuthority,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        owner: a.publicKey,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    balance = await provider.connection.getTokenAccountBalance(holderTokenAccount);
+    expect(balance.value.amount).to.equal("0");
+    ledger = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.totalScore.toNumber()).to.equal(0);
+    // The emptied mint is closed, so the receipt's mint address is free again
+    expect(await provider.connection.getAccountInfo(badgeMint)).to.be.null;
+
+    await claim();
+    balance = await provider.connection.getTokenAccountBalance(holderTokenAccount);
+    expect(balance.value.amount).to.equal("1");
+    ledger = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.categoryScores[4].toNumber()).to.equal(25);
+  });
+});
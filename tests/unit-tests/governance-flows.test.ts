@@ -39,7 +39,425 @@ describe("Governance Flows Unit", () => {
       .signers([admin])
       .rpc();
 
+    await program.methods
+      .setReputationScope(true)
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const realmAcc: any = await program.account.governanceRealm.fetch(realm);
+    expect(realmAcc.reputationAlgorithm.communityWeight).to.equal(120);
+    expect(realmAcc.localReputationOnly).to.be.true;
+  });
+  it("Require M-of-N admin approvals once the threshold is raised", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const second = anchor.web3.Keypair.generate();
+    for (const kp of [admin, second]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 2e9),
+        "confirmed"
+      );
+    }
+
+    const name = "realm-multisig";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const actionPda = (id: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_action"), realm.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    const propose = (id: number, kind: any) =>
+      program.methods
+        .proposeAdminAction(kind)
+        .accounts({
+          realm,
+          adminAction: actionPda(id),
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    const proposeAndExecute = async (id: number, kind: any) => {
+      await propose(id, kind);
+      await program.methods
+        .executeAdminAction()
+        .accounts({ realm, adminAction: actionPda(id), executor: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    };
+
+    // Threshold 1: the proposer's own approval is enough
+    await proposeAndExecute(0, { addAdmin: { admin: second.publicKey } });
+    await proposeAndExecute(1, { setThreshold: { adminThreshold: 2 } });
+
+    try {
+      await program.methods
+        .updateAlgorithm([200, 150, 120, 130, 110], 3, true, 12)
+        .accounts({ realm, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      expect.fail("single admin updated a 2-of-2 realm");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("MultisigRequired");
+    }
+
+    const kind = {
+      updateAlgorithm: {
+        algorithmWeights: [200, 150, 120, 130, 110],
+        decayFactor: 3,
+        aiEnhancement: true,
+        crossRealmFactor: 12,
+      },
+    };
+    try {
+      await proposeAndExecute(2, kind);
+      expect.fail("executed with one of two approvals");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("AdminActionNotApproved");
+    }
+    await program.methods
+      .approveAdminAction()
+      .accounts({ realm, adminAction: actionPda(2), admin: second.publicKey })
+      .signers([second])
+      .rpc();
+    await program.methods
+      .executeAdminAction()
+      .accounts({ realm, adminAction: actionPda(2), executor: second.publicKey })
+      .signers([second])
+      .rpc();
+
+    // Every realm setting, not just the algorithm, needs both admins
+    try {
+      await program.methods
+        .setDelegationDepth(0)
+        .accounts({ realm, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      expect.fail("single admin changed a 2-of-2 realm's delegation depth");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("MultisigRequired");
+    }
+    await propose(3, { setDelegationDepth: { maxDelegationDepth: 0 } });
+    await program.methods
+      .approveAdminAction()
+      .accounts({ realm, adminAction: actionPda(3), admin: second.publicKey })
+      .signers([second])
+      .rpc();
+    await program.methods
+      .executeAdminAction()
+      .accounts({ realm, adminAction: actionPda(3), executor: second.publicKey })
+      .signers([second])
+      .rpc();
+
+    // Instructions without an AdminAction route take the co-signers directly
+    const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_def"), realm.toBuffer(), Buffer.from([0, 0])],
+      program.programId
+    );
+    const createBadgeDefinition = (coSigners: anchor.web3.Keypair[]) =>
+      program.methods
+        .createBadgeDefinition({
+          badgeType: 4,
+          name: "Security Auditor",
+          uri: "",
+          scoreBonus: new anchor.BN(25),
+          category: 4,
+          maxSupply: 0,
+          expiresAt: new anchor.BN(0),
+          validitySeconds: new anchor.BN(0),
+          eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+          criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+          issuer: admin.publicKey,
+          merkleRoot: Array(32).fill(0),
+        })
+        .accounts({
+          realm,
+          badgeDefinition,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(coSigners.map((kp) => ({ pubkey: kp.publicKey, isWritable: false, isSigner: true })))
+        .signers([admin, ...coSigners])
+        .rpc();
+    try {
+      await createBadgeDefinition([]);
+      expect.fail("single admin defined a badge in a 2-of-2 realm");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("AdminQuorumRequired");
+    }
+    await createBadgeDefinition([second]);
+
     const realmAcc: any = await program.account.governanceRealm.fetch(realm);
+    expect(realmAcc.adminThreshold).to.equal(2);
     expect(realmAcc.reputationAlgorithm.communityWeight).to.equal(120);
+    expect(realmAcc.maxDelegationDepth).to.equal(0);
+    expect(realmAcc.badgeDefinitionCount).to.equal(1);
+
+    // Approvals given before an admin change no longer count, even once the
+    // rotated-out key is back; the stale action can then be closed by anyone
+    const third = anchor.web3.Keypair.generate();
+    await propose(4, { setDelegationDepth: { maxDelegationDepth: 2 } });
+    for (const [id, oldAdmin, newAdmin, approver] of [
+      [5, second, third, second],
+      [6, third, second, third],
+    ] as [number, anchor.web3.Keypair, anchor.web3.Keypair, anchor.web3.Keypair][]) {
+      await propose(id, { rotateAdmin: { oldAdmin: oldAdmin.publicKey, newAdmin: newAdmin.publicKey } });
+      await program.methods
+        .approveAdminAction()
+        .accounts({ realm, adminAction: actionPda(id), admin: approver.publicKey })
+        .signers([approver])
+        .rpc();
+      await program.methods
+        .executeAdminAction()
+        .accounts({ realm, adminAction: actionPda(id), executor: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    }
+    try {
+      await program.methods
+        .approveAdminAction()
+        .accounts({ realm, adminAction: actionPda(4), admin: second.publicKey })
+        .signers([second])
+        .rpc();
+      expect.fail("approved an action proposed under the old admin set");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("AdminActionStale");
+    }
+
+    const closeAction = (id: number, closer: anchor.web3.Keypair) =>
+      program.methods
+        .closeAdminAction()
+        .accounts({ realm, adminAction: actionPda(id), proposer: admin.publicKey, closer: closer.publicKey })
+        .signers([closer])
+        .rpc();
+    const outsider = anchor.web3.Keypair.generate();
+    await closeAction(4, outsider);
+    expect(await provider.connection.getAccountInfo(actionPda(4))).to.be.null;
+
+    // A live action can only be cancelled by an admin
+    await propose(7, { setDelegationDepth: { maxDelegationDepth: 2 } });
+    try {
+      await closeAction(7, outsider);
+      expect.fail("outsider cancelled a pending action");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("AdminRequired");
+    }
+    await closeAction(7, second);
+    expect(await provider.connection.getAccountInfo(actionPda(7))).to.be.null;
+  });
+  it("Join and leave a realm", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const member = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(member.publicKey, 2e9),
+      "confirmed"
+    );
+
+    const name = "realm-members";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    const [memberProfile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), member.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeProfile()
+      .accounts({
+        profile: memberProfile,
+        user: member.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([member])
+      .rpc();
+
+    // An endorsement lifts the score past the default 50 point threshold
+    const sponsor = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(sponsor.publicKey, 2e9),
+      "confirmed"
+    );
+    const [sponsorProfile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), sponsor.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeProfile()
+      .accounts({
+        profile: sponsorProfile,
+        user: sponsor.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+    await program.methods
+      .recordInteraction(0, 1000, "sponsor")
+      .accounts({
+        fromProfile: sponsorProfile,
+        toProfile: memberProfile,
+        interactionEvent: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("interaction"),
+            sponsor.publicKey.toBuffer(),
+            member.publicKey.toBuffer(),
+            new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0],
+        interactionPair: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("pair"), sponsor.publicKey.toBuffer(), member.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        fromUser: sponsor.publicKey,
+        toUser: member.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+
+    const [realmReputation] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm_rep"), realm.toBuffer(), member.publicKey.toBuffer()],
+      program.programId
+    );
+    const scoreBeforeBadges: any = await program.account.reputationProfile.fetch(memberProfile);
+
+    // Badges claimed before joining open the realm ledger and credit only it
+    for (const badgeType of [1, 2]) {
+      const definitionId = Buffer.alloc(2);
+      definitionId.writeUInt16LE(badgeType - 1);
+      const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("badge_def"), realm.toBuffer(), definitionId],
+        program.programId
+      );
+      const [badge] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("badge"),
+          member.publicKey.toBuffer(),
+          badgeDefinition.toBuffer(),
+        ],
+        program.programId
+      );
+      await program.methods
+        .createBadgeDefinition({
+          badgeType: badgeType,
+          name: `Badge ${badgeType}`,
+          uri: "",
+          scoreBonus: new anchor.BN(25),
+          category: badgeType - 1,
+          maxSupply: 0,
+          expiresAt: new anchor.BN(0),
+          validitySeconds: new anchor.BN(0),
+          eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+          criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+          issuer: admin.publicKey,
+          merkleRoot: Array(32).fill(0),
+        })
+        .accounts({
+          realm,
+          badgeDefinition,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      await program.methods
+        .claimBadge(Array(32).fill(badgeType), [])
+        .accounts({
+          profile: memberProfile,
+          badgeAccount: badge,
+          realm,
+          badgeDefinition,
+          issuer: admin.publicKey,
+          realmReputation,
+          user: member.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([member, admin])
+        .rpc();
+    }
+    const scoreAfterBadges: any = await program.account.reputationProfile.fetch(memberProfile);
+    expect(scoreAfterBadges.totalScore.toNumber()).to.equal(scoreBeforeBadges.totalScore.toNumber());
+    expect(scoreAfterBadges.badgeCount).to.equal(2);
+    await program.methods
+      .joinRealm()
+      .accounts({
+        realm,
+        profile: memberProfile,
+        realmReputation,
+        member: member.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([member])
+      .rpc();
+
+    let realmAcc: any = await program.account.governanceRealm.fetch(realm);
+    let profileAcc: any = await program.account.reputationProfile.fetch(memberProfile);
+    expect(realmAcc.totalMembers).to.equal(1);
+    expect(profileAcc.realmMemberships[0].toString()).to.equal(realm.toString());
+    const ledger: any = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.wallet.toString()).to.equal(member.publicKey.toString());
+    // Joining keeps the badge bonuses already on the ledger
+    expect(ledger.totalScore.toNumber()).to.equal(50);
+
+    await program.methods
+      .removeMember()
+      .accounts({
+        realm,
+        memberProfile,
+        member: member.publicKey,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    realmAcc = await program.account.governanceRealm.fetch(realm);
+    profileAcc = await program.account.reputationProfile.fetch(memberProfile);
+    expect(realmAcc.totalMembers).to.equal(0);
+    expect(profileAcc.realmMemberships[0].toString()).to.equal(
+      anchor.web3.PublicKey.default.toString()
+    );
+
+    try {
+      await program.methods
+        .leaveRealm()
+        .accounts({ realm, profile: memberProfile, member: member.publicKey })
+        .signers([member])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NotRealmMember");
+    }
   });
 });
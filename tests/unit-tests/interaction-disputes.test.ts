@@ -0,0 +1,274 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+describe("Interaction Retention and Disputes Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  const pda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const profileOf = (kp: anchor.web3.Keypair) => pda([Buffer.from("reputation"), kp.publicKey.toBuffer()]);
+  const eventOf = (from: anchor.web3.Keypair, to: anchor.web3.Keypair, nonce: number) =>
+    pda([
+      Buffer.from("interaction"),
+      from.publicKey.toBuffer(),
+      to.publicKey.toBuffer(),
+      new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+    ]);
+
+  const [admin, a, b, c, d] = [0, 1, 2, 3, 4].map(() => anchor.web3.Keypair.generate());
+  const name = "realm-disputes";
+  const realm = pda([Buffer.from("realm"), Buffer.from(name)]);
+  const ledgerOf = (kp: anchor.web3.Keypair) =>
+    pda([Buffer.from("realm_rep"), realm.toBuffer(), kp.publicKey.toBuffer()]);
+  const DAY = 86_400;
+
+  // Every sender sends once, so the sender-wide interval never trips
+  const interact = (
+    from: anchor.web3.Keypair,
+    to: anchor.web3.Keypair,
+    scoped: { realm: anchor.web3.PublicKey | null; toRealmReputation: anchor.web3.PublicKey | null }
+  ) =>
+    program.methods
+      .recordInteraction(0, 1000, "endorse")
+      .accounts({
+        fromProfile: profileOf(from),
+        toProfile: profileOf(to),
+        interactionEvent: eventOf(from, to, 0),
+        interactionPair: pda([Buffer.from("pair"), from.publicKey.toBuffer(), to.publicKey.toBuffer()]),
+        fromUser: from.publicKey,
+        toUser: to.publicKey,
+        ...scoped,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([from])
+      .rpc();
+
+  const setRetention = (retentionSeconds: number) =>
+    program.methods
+      .setInteractionRetention(retentionSeconds)
+      .accounts({ realm, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [admin, a, b, c, d]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 2e9),
+        "confirmed"
+      );
+    }
+    for (const kp of [a, b, c, d]) {
+      await program.methods
+        .initializeProfile()
+        .accounts({ profile: profileOf(kp), user: kp.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([kp])
+        .rpc();
+    }
+    await interact(a, b, { realm: null, toRealmReputation: null });
+    await interact(b, c, { realm: null, toRealmReputation: null });
+    await interact(c, d, { realm: null, toRealmReputation: null });
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    for (const kp of [c, d]) {
+      await program.methods
+        .joinRealm()
+        .accounts({
+          realm,
+          profile: profileOf(kp),
+          realmReputation: ledgerOf(kp),
+          member: kp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("Keeps retention at least as long as the dispute window", async () => {
+    try {
+      await setRetention(DAY);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("RetentionTooShort");
+    }
+    await setRetention(20 * DAY);
+    const realmAcc: any = await program.account.governanceRealm.fetch(realm);
+    expect(realmAcc.interactionRetentionSeconds).to.equal(20 * DAY);
+  });
+
+  it("Fixes an event's expiry when it is recorded", async () => {
+    await interact(d, c, { realm, toRealmReputation: ledgerOf(c) });
+    const event: any = await program.account.interactionEvent.fetch(eventOf(d, c, 0));
+    expect(event.expiresAt.toNumber()).to.equal(event.timestamp.toNumber() + 20 * DAY);
+
+    // Realm-less events use the default retention
+    const plain: any = await program.account.interactionEvent.fetch(eventOf(a, b, 0));
+    expect(plain.expiresAt.toNumber()).to.equal(plain.timestamp.toNumber() + 30 * DAY);
+
+    // Raising the realm's retention later does not reach back
+    await setRetention(40 * DAY);
+    const after: any = await program.account.interactionEvent.fetch(eventOf(d, c, 0));
+    expect(after.expiresAt.toNumber()).to.equal(event.expiresAt.toNumber());
+
+    try {
+      await program.methods
+        .closeInteractionEvent()
+        .accounts({ interactionEvent: eventOf(d, c, 0), fromUser: d.publicKey, caller: d.publicKey })
+        .signers([d])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("RetentionPeriodActive");
+    }
+  });
+
+  describe("Disputes", () => {
+    const event = () => eventOf(d, c, 0);
+    const disputeOf = (event: anchor.web3.PublicKey, arbiter: anchor.web3.PublicKey) =>
+      pda([Buffer.from("dispute"), event.toBuffer(), arbiter.toBuffer()]);
+    const dispute = () => disputeOf(event(), realm);
+    const open = (
+      opener: anchor.web3.Keypair,
+      event: anchor.web3.PublicKey,
+      receiver: anchor.web3.Keypair,
+      arbiter: anchor.web3.PublicKey
+    ) =>
+      program.methods
+        .openDispute("spam endorsement")
+        .accounts({
+          interactionEvent: event,
+          dispute: disputeOf(event, arbiter),
+          realm: arbiter,
+          receiverProfile: profileOf(receiver),
+          openerProfile: profileOf(opener),
+          opener: opener.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([opener])
+        .rpc();
+
+    it("Only lets the event's own realm arbitrate", async () => {
+      // A realm anyone can create must not arbitrate another realm's event...
+      const outsider = anchor.web3.Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(outsider.publicKey, 2e9),
+        "confirmed"
+      );
+      await program.methods
+        .initializeProfile()
+        .accounts({ profile: profileOf(outsider), user: outsider.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      const foreign = pda([Buffer.from("realm"), Buffer.from("realm-disputes-foreign")]);
+      await program.methods
+        .createRealm("realm-disputes-foreign", [100, 100, 100, 100, 100])
+        .accounts({ realm: foreign, admin: outsider.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([outsider])
+        .rpc();
+      try {
+        await open(outsider, event(), c, foreign);
+        expect.fail("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("RealmMismatch");
+      }
+
+      // ...nor a realm-less one whose receiver is not a member
+      try {
+        await open(outsider, eventOf(a, b, 0), b, foreign);
+        expect.fail("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("DisputeRealmRequired");
+      }
+    });
+
+    it("Opens and upholds a dispute in the event's realm", async () => {
+      await open(c, event(), c, realm);
+      const opened: any = await program.account.dispute.fetch(dispute());
+      expect(opened.status).to.deep.equal({ open: {} });
+      expect(opened.realm.toBase58()).to.equal(realm.toBase58());
+      const disputed: any = await program.account.interactionEvent.fetch(event());
+      expect(disputed.openDisputes).to.equal(1);
+
+      await program.methods
+        .resolveDispute(true, new anchor.BN(10))
+        .accounts({ dispute: dispute(), interactionEvent: event(), realm, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      const upheld: any = await program.account.dispute.fetch(dispute());
+      expect(upheld.status).to.deep.equal({ upheld: {} });
+      expect(upheld.trustSlash.toNumber()).to.equal(10);
+    });
+
+    it("Reverses the global and realm credit", async () => {
+      const evt: any = await program.account.interactionEvent.fetch(event());
+      const profileBefore: any = await program.account.reputationProfile.fetch(profileOf(c));
+      const ledgerBefore: any = await program.account.realmReputation.fetch(ledgerOf(c));
+      const senderBefore: any = await program.account.reputationProfile.fetch(profileOf(d));
+
+      await program.methods
+        .reverseInteraction()
+        .accounts({
+          dispute: dispute(),
+          interactionEvent: event(),
+          fromProfile: profileOf(d),
+          toProfile: profileOf(c),
+          toRealmReputation: ledgerOf(c),
+          caller: c.publicKey,
+        })
+        .signers([c])
+        .rpc();
+
+      const profileAfter: any = await program.account.reputationProfile.fetch(profileOf(c));
+      const ledgerAfter: any = await program.account.realmReputation.fetch(ledgerOf(c));
+      const senderAfter: any = await program.account.reputationProfile.fetch(profileOf(d));
+      expect(evt.realmDelta.toNumber()).to.be.greaterThan(0);
+      expect(profileAfter.totalScore.toNumber()).to.equal(
+        profileBefore.totalScore.toNumber() - evt.reputationDelta.toNumber()
+      );
+      expect(ledgerAfter.totalScore.toNumber()).to.equal(
+        ledgerBefore.totalScore.toNumber() - evt.realmDelta.toNumber()
+      );
+      expect(senderAfter.trustMultiplier.toNumber()).to.equal(senderBefore.trustMultiplier.toNumber() - 10);
+
+      const reversed: any = await program.account.interactionEvent.fetch(event());
+      expect(reversed.reversed).to.be.true;
+      const done: any = await program.account.dispute.fetch(dispute());
+      expect(done.status).to.deep.equal({ reversed: {} });
+    });
+
+    it("Arbitrates a realm-less event in a realm the receiver belongs to", async () => {
+      const plain = eventOf(c, d, 0);
+      const arbitrated = disputeOf(plain, realm);
+      await open(d, plain, d, realm);
+      await program.methods
+        .resolveDispute(true, new anchor.BN(0))
+        .accounts({ dispute: arbitrated, interactionEvent: plain, realm, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      const evt: any = await program.account.interactionEvent.fetch(plain);
+      const before: any = await program.account.reputationProfile.fetch(profileOf(d));
+      await program.methods
+        .reverseInteraction()
+        .accounts({
+          dispute: arbitrated,
+          interactionEvent: plain,
+          fromProfile: profileOf(c),
+          toProfile: profileOf(d),
+          toRealmReputation: null,
+          caller: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      const after: any = await program.account.reputationProfile.fetch(profileOf(d));
+      expect(after.totalScore.toNumber()).to.equal(before.totalScore.toNumber() - evt.reputationDelta.toNumber());
+    });
+  });
+});
@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+describe("Proposal Lifecycle Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  const pda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const profileOf = (kp: anchor.web3.Keypair) =>
+    pda([Buffer.from("reputation"), kp.publicKey.toBuffer()]);
+
+  const admin = anchor.web3.Keypair.generate();
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const carol = anchor.web3.Keypair.generate();
+  const name = "realm-lifecycle";
+  const realm = pda([Buffer.from("realm"), Buffer.from(name)]);
+  const proposal = pda([Buffer.from("proposal"), realm.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)]);
+  const voteRecordOf = (kp: anchor.web3.Keypair) =>
+    pda([Buffer.from("vote"), proposal.toBuffer(), kp.publicKey.toBuffer()]);
+
+  // One max-weight endorsement is worth the capped 200 points
+  const endorse = async (from: anchor.web3.Keypair, to: anchor.web3.Keypair) => {
+    await program.methods
+      .recordInteraction(0, 1000, "endorse")
+      .accounts({
+        fromProfile: profileOf(from),
+        toProfile: profileOf(to),
+        interactionEvent: pda([
+          Buffer.from("interaction"),
+          from.publicKey.toBuffer(),
+          to.publicKey.toBuffer(),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+        ]),
+        interactionPair: pda([Buffer.from("pair"), from.publicKey.toBuffer(), to.publicKey.toBuffer()]),
+        fromUser: from.publicKey,
+        toUser: to.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([from])
+      .rpc();
+  };
+
+  const vote = (voter: anchor.web3.Keypair, voteType: number) =>
+    program.methods
+      .castVote(voteType)
+      .accounts({
+        realm,
+        proposal,
+        voterProfile: profileOf(voter),
+        voteRecord: voteRecordOf(voter),
+        voter: voter.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [admin, alice, bob, carol]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 2e9),
+        "confirmed"
+      );
+    }
+    for (const kp of [alice, bob, carol]) {
+      await program.methods
+        .initializeProfile()
+        .accounts({
+          profile: profileOf(kp),
+          user: kp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+    }
+    await endorse(alice, bob);
+    await endorse(bob, alice);
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    for (const kp of [alice, bob]) {
+      await program.methods
+        .joinRealm()
+        .accounts({
+          realm,
+          profile: profileOf(kp),
+          realmReputation: pda([Buffer.from("realm_rep"), realm.toBuffer(), kp.publicKey.toBuffer()]),
+          member: kp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+    }
+  });
+
+  it("Weighs votes by the score held when the proposal was created", async () => {
+    await program.methods
+      .createProposal("Fund audits", "Pay for two audits this quarter", 255)
+      .accounts({
+        realm,
+        proposerProfile: profileOf(alice),
+        proposal,
+        proposer: alice.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const created: any = await program.account.proposal.fetch(proposal);
+    expect(created.proposalId.toNumber()).to.equal(0);
+    expect(created.category).to.equal(255);
+    expect(created.status).to.deep.equal({ active: {} });
+
+    // Reputation Bob gains once the proposal is open adds no weight
+    const before: any = await program.account.reputationProfile.fetch(profileOf(bob));
+    await endorse(carol, bob);
+    const after: any = await program.account.reputationProfile.fetch(profileOf(bob));
+    expect(after.totalScore.toNumber()).to.be.greaterThan(before.totalScore.toNumber());
+
+    await vote(bob, 0);
+    const tallied: any = await program.account.proposal.fetch(proposal);
+    expect(tallied.yesVotes.toNumber()).to.equal(before.totalScore.toNumber());
+  });
+
+  it("Tallies live weight and rejects double votes", async () => {
+    await vote(alice, 1);
+
+    const profile: any = await program.account.reputationProfile.fetch(profileOf(alice));
+    const tallied: any = await program.account.proposal.fetch(proposal);
+    expect(tallied.noVotes.toNumber()).to.equal(profile.totalScore.toNumber());
+    expect(tallied.voterCount).to.equal(2);
+
+    const record: any = await program.account.voteRecord.fetch(voteRecordOf(alice));
+    expect(record.castBy.toBase58()).to.equal(alice.publicKey.toBase58());
+
+    try {
+      await vote(alice, 0);
+      expect.fail("voted twice");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("AlreadyVoted");
+    }
+  });
+
+  it("Only finalizes once voting has ended", async () => {
+    try {
+      await program.methods
+        .finalizeProposal()
+        .accounts({ realm, proposal, caller: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      expect.fail("finalized during the voting period");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("VotingPeriodActive");
+    }
+    const still: any = await program.account.proposal.fetch(proposal);
+    expect(still.status).to.deep.equal({ active: {} });
+  });
+});
@@ -0,0 +1,180 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+
+describe("Reputation Attestations Unit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolanaReputationDao as Program;
+
+  it("Imports attestations signed by the realm's authority only", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const member = anchor.web3.Keypair.generate();
+    const sponsor = anchor.web3.Keypair.generate();
+    const authority = anchor.web3.Keypair.generate();
+    const forger = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(member.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(sponsor.publicKey, 2e9),
+      "confirmed"
+    );
+
+    const name = "realm-attest";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    const [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), member.publicKey.toBuffer()],
+      program.programId
+    );
+    const [sponsorProfile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), sponsor.publicKey.toBuffer()],
+      program.programId
+    );
+    const [realmReputation] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm_rep"), realm.toBuffer(), member.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setBridgePolicy(new anchor.BN(500), authority.publicKey)
+      .accounts({ realm, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+    for (const [kp, p] of [[member, profile], [sponsor, sponsorProfile]] as const) {
+      await program.methods
+        .initializeProfile()
+        .accounts({
+          profile: p,
+          user: kp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+    }
+
+    // As exported by another program instance
+    const attestation = {
+      sourceProgram: anchor.web3.Keypair.generate().publicKey,
+      sourceRealm: anchor.web3.Keypair.generate().publicKey,
+      wallet: member.publicKey,
+      totalScore: new anchor.BN(1000),
+      categoryScores: Array(5).fill(new anchor.BN(200)),
+      slot: new anchor.BN(1),
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+    };
+    const message = program.coder.types.encode("ReputationAttestation", attestation);
+    const [bridgeRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bridge"), profile.toBuffer(), attestation.sourceRealm.toBuffer(), realm.toBuffer()],
+      program.programId
+    );
+    const importWith = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .importReputationAttestation(attestation)
+        .accounts({
+          profile,
+          bridgeRecord,
+          realm,
+          realmReputation,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          user: member.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([
+          anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message,
+          }),
+        ])
+        .signers([member])
+        .rpc();
+
+    // Only members of the destination realm may import into it
+    try {
+      await importWith(authority);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AccountNotInitialized");
+    }
+    await program.methods
+      .recordInteraction(0, 1000, "endorse")
+      .accounts({
+        fromProfile: sponsorProfile,
+        toProfile: profile,
+        interactionEvent: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("interaction"),
+            sponsor.publicKey.toBuffer(),
+            member.publicKey.toBuffer(),
+            new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0],
+        interactionPair: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("pair"), sponsor.publicKey.toBuffer(), member.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        fromUser: sponsor.publicKey,
+        toUser: member.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+    await program.methods
+      .joinRealm()
+      .accounts({
+        realm,
+        profile,
+        realmReputation,
+        member: member.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([member])
+      .rpc();
+
+    try {
+      await importWith(forger);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidAttestation");
+    }
+
+    const before: any = await program.account.reputationProfile.fetch(profile);
+    await importWith(authority);
+    // 1000 * cross_realm_factor 10%, split like the attested categories
+    const ledger: any = await program.account.realmReputation.fetch(realmReputation);
+    expect(ledger.totalScore.toNumber()).to.equal(100);
+    expect(ledger.categoryScores.map((s: any) => s.toNumber())).to.deep.equal([20, 20, 20, 20, 20]);
+    // The global score is never credited by an import, only the cross-DAO tally
+    const acc: any = await program.account.reputationProfile.fetch(profile);
+    expect(acc.totalScore.toNumber()).to.equal(before.totalScore.toNumber());
+    expect(acc.crossDaoReputation.toNumber()).to.equal(before.crossDaoReputation.toNumber() + 100);
+
+    // The same signed attestation cannot be imported twice
+    try {
+      await importWith(authority);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("BridgeReplay");
+    }
+    const record: any = await program.account.bridgeRecord.fetch(bridgeRecord);
+    expect(record.totalBridged.toNumber()).to.equal(100);
+  });
+});
@@ -42,18 +42,31 @@ describe("Reputation Calculations Unit", () => {
       .signers([b])
       .rpc();
 
-    const evt = anchor.web3.Keypair.generate();
+    const [evt] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("interaction"),
+        a.publicKey.toBuffer(),
+        b.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [pair] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pair"), a.publicKey.toBuffer(), b.publicKey.toBuffer()],
+      program.programId
+    );
     await program.methods
       .recordInteraction(7, 200, "Major innovation")
       .accounts({
         fromProfile: aP,
         toProfile: bP,
-        interactionEvent: evt.publicKey,
+        interactionEvent: evt,
+        interactionPair: pair,
         fromUser: a.publicKey,
         toUser: b.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .signers([a, evt])
+      .signers([a])
       .rpc();
 
     const bAcc: any = await program.account.reputationProfile.fetch(bP);
@@ -26,23 +26,428 @@ describe("Security Validations Unit", () => {
       .signers([a])
       .rpc();
 
-    const evt = anchor.web3.Keypair.generate();
+    const [evt] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("interaction"),
+        a.publicKey.toBuffer(),
+        a.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [pair] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pair"), a.publicKey.toBuffer(), a.publicKey.toBuffer()],
+      program.programId
+    );
     try {
       await program.methods
         .recordInteraction(0, 100, "Self")
         .accounts({
           fromProfile: aP,
           toProfile: aP,
-          interactionEvent: evt.publicKey,
+          interactionEvent: evt,
+          interactionPair: pair,
           fromUser: a.publicKey,
           toUser: a.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([a, evt])
+        .signers([a])
         .rpc();
       expect.fail("Should have failed");
     } catch (e: any) {
       expect(e.toString()).to.include("SelfInteractionForbidden");
     }
   });
+  it("Rejects badge claims without the issuer's co-signature", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const a = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(a.publicKey, 2e9),
+      "confirmed"
+    );
+
+    const name = "realm-badge-sec";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    const [aP] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), a.publicKey.toBuffer()],
+      program.programId
+    );
+    const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_def"), realm.toBuffer(), Buffer.from([0, 0])],
+      program.programId
+    );
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .createBadgeDefinition({
+        badgeType: 4,
+        name: "Security Auditor",
+        uri: "",
+        scoreBonus: new anchor.BN(25),
+        category: 4,
+        maxSupply: 0,
+        expiresAt: new anchor.BN(0),
+        validitySeconds: new anchor.BN(0),
+        eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+        criteria: { autoAward: false, minCategoryScores: Array(5).fill(new anchor.BN(0)), minInteractions: 0, minVotesCast: 0, minCrossDaoReputation: new anchor.BN(0) },
+        issuer: admin.publicKey,
+        merkleRoot: Array(32).fill(0),
+      })
+      .accounts({
+        realm,
+        badgeDefinition,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .initializeProfile()
+      .accounts({
+        profile: aP,
+        user: a.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([a])
+      .rpc();
+
+    const [badge] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge"), a.publicKey.toBuffer(), badgeDefinition.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .claimBadge(Array(32).fill(1), [])
+        .accounts({
+          profile: aP,
+          badgeAccount: badge,
+          realm,
+          badgeDefinition,
+          issuer: null,
+          realmReputation: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("realm_rep"), realm.toBuffer(), a.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          user: a.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([a])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidBadgeProof");
+    }
+  });
+
+  it("Only awards criteria badges to profiles that meet them", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    const a = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(a.publicKey, 2e9),
+      "confirmed"
+    );
+
+    const name = "realm-badge-crit";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    const [aP] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), a.publicKey.toBuffer()],
+      program.programId
+    );
+    const [badgeDefinition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_def"), realm.toBuffer(), Buffer.from([0, 0])],
+      program.programId
+    );
+    const [badge] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("badge"), a.publicKey.toBuffer(), badgeDefinition.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({
+        realm,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const define = (minGovernance: number) =>
+      program.methods
+        .createBadgeDefinition({
+          badgeType: 2,
+          name: "Governance Participant",
+          uri: "",
+          scoreBonus: new anchor.BN(25),
+          category: 1,
+          maxSupply: 0,
+          expiresAt: new anchor.BN(0),
+          validitySeconds: new anchor.BN(0),
+          eligibility: { minTotalScore: new anchor.BN(0), minCategoryScore: new anchor.BN(0), membersOnly: false },
+          criteria: {
+            autoAward: true,
+            minCategoryScores: [0, minGovernance, 0, 0, 0].map((n) => new anchor.BN(n)),
+            minInteractions: 0,
+            minVotesCast: 1,
+            minCrossDaoReputation: new anchor.BN(0),
+          },
+          issuer: anchor.web3.PublicKey.default,
+          merkleRoot: Array(32).fill(0),
+        })
+        .accounts({
+          realm,
+          badgeDefinition,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+    // Counters alone are too cheap to reach when anyone can trigger the award
+    try {
+      await define(0);
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AutoAwardCriteriaTooWeak");
+    }
+
+    // Governance Participant: earned governance reputation and at least one proposal vote
+    await define(100);
+
+    await program.methods
+      .initializeProfile()
+      .accounts({
+        profile: aP,
+        user: a.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([a])
+      .rpc();
+
+    try {
+      await program.methods
+        .awardEarnedBadge()
+        .accounts({
+          profile: aP,
+          badgeAccount: badge,
+          realm,
+          badgeDefinition,
+          badgeCollection: null,
+          realmReputation: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("realm_rep"), realm.toBuffer(), a.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          owner: a.publicKey,
+          payer: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("BadgeCriteriaNotMet");
+    }
+  });
+
+  it("Binds realm-scoped interactions to members on both sides", async () => {
+    const pda = (seeds: Buffer[]) =>
+      anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const [admin, a, b, c, d] = [0, 1, 2, 3, 4].map(() => anchor.web3.Keypair.generate());
+    for (const kp of [admin, a, b, c, d]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(kp.publicKey, 2e9),
+        "confirmed"
+      );
+    }
+    const profileOf = (kp: anchor.web3.Keypair) => pda([Buffer.from("reputation"), kp.publicKey.toBuffer()]);
+    const name = "realm-bound-interactions";
+    const realm = pda([Buffer.from("realm"), Buffer.from(name)]);
+    const ledgerOf = (kp: anchor.web3.Keypair) =>
+      pda([Buffer.from("realm_rep"), realm.toBuffer(), kp.publicKey.toBuffer()]);
+
+    const interact = async (
+      from: anchor.web3.Keypair,
+      to: anchor.web3.Keypair,
+      interactionType: number,
+      weight: number,
+      scoped: { realm: anchor.web3.PublicKey | null; toRealmReputation: anchor.web3.PublicKey | null }
+    ) => {
+      const fromAcc: any = await program.account.reputationProfile.fetch(profileOf(from));
+      await program.methods
+        .recordInteraction(interactionType, weight, "")
+        .accounts({
+          fromProfile: profileOf(from),
+          toProfile: profileOf(to),
+          interactionEvent: pda([
+            Buffer.from("interaction"),
+            from.publicKey.toBuffer(),
+            to.publicKey.toBuffer(),
+            fromAcc.interactionNonce.toArrayLike(Buffer, "le", 8),
+          ]),
+          interactionPair: pda([Buffer.from("pair"), from.publicKey.toBuffer(), to.publicKey.toBuffer()]),
+          fromUser: from.publicKey,
+          toUser: to.publicKey,
+          ...scoped,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([from])
+        .rpc();
+    };
+    const join = (kp: anchor.web3.Keypair) =>
+      program.methods
+        .joinRealm()
+        .accounts({
+          realm,
+          profile: profileOf(kp),
+          realmReputation: ledgerOf(kp),
+          member: kp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+    const global = { realm: null, toRealmReputation: null };
+
+    for (const kp of [a, b, c, d]) {
+      await program.methods
+        .initializeProfile()
+        .accounts({ profile: profileOf(kp), user: kp.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([kp])
+        .rpc();
+    }
+    // Each sender sends once, keeping clear of the sender-wide interval
+    await interact(a, b, 0, 1000, global);
+    await interact(b, c, 0, 1000, global);
+    await interact(c, d, 0, 1000, global);
+
+    // Community interactions earn nothing inside this realm
+    await program.methods
+      .createRealm(name, [100, 100, 0, 100, 100])
+      .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    await join(d);
+
+    try {
+      await interact(d, c, 1, 20, { realm, toRealmReputation: null });
+      expect.fail("credited a non-member through the realm");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NotRealmMember");
+    }
+
+    await join(c);
+    try {
+      await interact(d, c, 1, 20, { realm, toRealmReputation: null });
+      expect.fail("skipped the receiver's ledger");
+    } catch (e: any) {
+      expect(e.toString()).to.include("RealmReputationRequired");
+    }
+
+    const before: any = await program.account.reputationProfile.fetch(profileOf(c));
+    await interact(d, c, 1, 20, { realm, toRealmReputation: ledgerOf(c) });
+    const after: any = await program.account.reputationProfile.fetch(profileOf(c));
+    const ledger: any = await program.account.realmReputation.fetch(ledgerOf(c));
+    // The realm's zero community weight applies to the global score as well
+    expect(after.totalScore.toNumber()).to.equal(before.totalScore.toNumber());
+    expect(ledger.totalScore.toNumber()).to.equal(0);
+
+    // Reputation votes always land in the voter's realm ledger too
+    const reputationVote = (voter: anchor.web3.Keypair) =>
+      program.methods
+        .castReputationVote(1, "")
+        .accounts({
+          voterProfile: profileOf(voter),
+          realm,
+          voterRealmReputation: ledgerOf(voter),
+          voter: voter.publicKey,
+        })
+        .signers([voter])
+        .rpc();
+    try {
+      await reputationVote(b);
+      expect.fail("voted without a realm ledger");
+    } catch (e: any) {
+      expect(e.toString()).to.include("AccountNotInitialized");
+    }
+    const voterBefore: any = await program.account.reputationProfile.fetch(profileOf(d));
+    await reputationVote(d);
+    const voterAfter: any = await program.account.reputationProfile.fetch(profileOf(d));
+    const voterLedger: any = await program.account.realmReputation.fetch(ledgerOf(d));
+    expect(voterAfter.totalScore.toNumber()).to.equal(voterBefore.totalScore.toNumber() + 15);
+    expect(voterLedger.categoryScores[1].toNumber()).to.equal(15);
+
+    // One reputation vote a day, so the increment cannot be farmed
+    try {
+      await reputationVote(d);
+      expect.fail("voted twice within the cooldown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ReputationVoteCooldown");
+    }
+    const voterLater: any = await program.account.reputationProfile.fetch(profileOf(d));
+    expect(voterLater.totalScore.toNumber()).to.equal(voterAfter.totalScore.toNumber());
+    const ledgerLater: any = await program.account.realmReputation.fetch(ledgerOf(d));
+    expect(ledgerLater.totalScore.toNumber()).to.equal(voterLedger.totalScore.toNumber());
+  });
+
+  it("Rejects out-of-range algorithm weights", async () => {
+    const admin = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, 2e9),
+      "confirmed"
+    );
+    const name = "realm-weight-bounds";
+    const [realm] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("realm"), Buffer.from(name)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .createRealm(name, [100, 100, 5000, 100, 100])
+        .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidAlgorithmWeights");
+    }
+
+    await program.methods
+      .createRealm(name, [100, 100, 100, 100, 100])
+      .accounts({ realm, admin: admin.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    try {
+      await program.methods
+        .updateAlgorithm([100, 100, 100, 100, 100], 90, true, 12)
+        .accounts({ realm, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidAlgorithmParameters");
+    }
+  });
 });